@@ -0,0 +1,288 @@
+use serde_json::Value;
+
+use crate::input::{push_present, InputRecord};
+
+/// Defaults used when a log line does not say which host or scheme served it.
+#[derive(Debug, Clone, Default)]
+pub struct LogDefaults {
+    pub host: Option<String>,
+    pub scheme: String,
+}
+
+/// Parses a Common Log Format line, optionally prefixed with an Apache
+/// `vhost:port` field and optionally followed by the Combined Log Format
+/// referrer and user agent.
+pub fn parse_clf_line(line: &str, defaults: &LogDefaults) -> Result<InputRecord, String> {
+    let bracket = line
+        .find(" [")
+        .ok_or_else(|| "missing [timestamp]".to_string())?;
+    let prefix: Vec<&str> = line[..bracket].split_whitespace().collect();
+    let (vhost, remote_addr) = match prefix.as_slice() {
+        [remote, _ident, _user] => (None, *remote),
+        [vhost, remote, _ident, _user] => (Some(*vhost), *remote),
+        _ => return Err("expected 'host ident user' before the timestamp".to_string()),
+    };
+
+    let rest = &line[bracket + 2..];
+    let timestamp_end = rest
+        .find(']')
+        .ok_or_else(|| "unterminated [timestamp]".to_string())?;
+    let timestamp = &rest[..timestamp_end];
+    let rest = rest[timestamp_end + 1..].trim_start();
+
+    let (request, rest) = take_quoted(rest).ok_or_else(|| "missing \"request\"".to_string())?;
+    let mut numbers = rest.split_whitespace();
+    let status = numbers.next().ok_or_else(|| "missing status".to_string())?;
+    let bytes = numbers.next().unwrap_or("-");
+    let after_numbers = rest
+        .trim_start()
+        .splitn(3, char::is_whitespace)
+        .nth(2)
+        .unwrap_or_default()
+        .trim_start();
+
+    let (referrer, user_agent) = match take_quoted(after_numbers) {
+        Some((referrer, rest)) => (Some(referrer), take_quoted(rest.trim_start()).map(|q| q.0)),
+        None => (None, None),
+    };
+
+    let (method, target) = split_request_line(&request)?;
+    let (host, port) = match vhost {
+        Some(vhost) => match vhost.rsplit_once(':') {
+            Some((host, port)) if port.chars().all(|c| c.is_ascii_digit()) => {
+                (Some(host.to_string()), Some(port))
+            }
+            _ => (Some(vhost.to_string()), None),
+        },
+        None => (defaults.host.clone(), None),
+    };
+    let scheme = match port {
+        Some("443") => "https",
+        _ => defaults.scheme.as_str(),
+    };
+    let url = build_url(scheme, host.as_deref(), port, target)?;
+
+    let mut record = InputRecord::new(url);
+    record.push_field("method", method);
+    record.push_field("status", status);
+    record.push_field("timestamp", timestamp);
+    record.push_field("remote_addr", remote_addr);
+    push_present(&mut record, "bytes", Some(bytes));
+    push_present(&mut record, "referrer", referrer.as_deref());
+    push_present(&mut record, "user_agent", user_agent.as_deref());
+    Ok(record)
+}
+
+/// Parses one JSON object per line as written by an nginx `log_format ... escape=json`.
+/// Common variable names (`request`, `request_uri`, `host`, `http_host`,
+/// `scheme`, `status`, `time_iso8601`, `http_referer`, ...) are recognised.
+pub fn parse_nginx_json_line(line: &str, defaults: &LogDefaults) -> Result<InputRecord, String> {
+    let value: Value = serde_json::from_str(line).map_err(|e| format!("invalid JSON: {e}"))?;
+    let object = value
+        .as_object()
+        .ok_or_else(|| "expected a JSON object".to_string())?;
+    let get = |keys: &[&str]| {
+        keys.iter().find_map(|key| match object.get(*key) {
+            Some(Value::String(s)) if !s.is_empty() && s != "-" => Some(s.clone()),
+            Some(Value::Number(n)) => Some(n.to_string()),
+            _ => None,
+        })
+    };
+
+    let (method, target) = match get(&["request"]) {
+        Some(request) => {
+            let (method, target) = split_request_line(&request)?;
+            (Some(method.to_string()), target.to_string())
+        }
+        None => {
+            let uri = get(&["request_uri", "uri", "path"])
+                .ok_or_else(|| "missing request, request_uri or uri".to_string())?;
+            let target = match get(&["args", "query_string"]) {
+                Some(args) if !uri.contains('?') => format!("{uri}?{args}"),
+                _ => uri,
+            };
+            (get(&["request_method", "method"]), target)
+        }
+    };
+
+    let host = get(&["http_host", "host", "server_name", "vhost"]).or(defaults.host.clone());
+    let scheme = get(&["scheme"]).unwrap_or_else(|| defaults.scheme.clone());
+    let url = build_url(&scheme, host.as_deref(), None, &target)?;
+
+    let mut record = InputRecord::new(url);
+    push_present(&mut record, "method", method.as_deref());
+    push_present(&mut record, "status", get(&["status"]).as_deref());
+    push_present(
+        &mut record,
+        "timestamp",
+        get(&[
+            "time_iso8601",
+            "time_local",
+            "timestamp",
+            "time",
+            "@timestamp",
+        ])
+        .as_deref(),
+    );
+    push_present(
+        &mut record,
+        "remote_addr",
+        get(&["remote_addr", "client_ip"]).as_deref(),
+    );
+    push_present(
+        &mut record,
+        "bytes",
+        get(&["body_bytes_sent", "bytes_sent"]).as_deref(),
+    );
+    push_present(
+        &mut record,
+        "referrer",
+        get(&["http_referer", "referer", "referrer"]).as_deref(),
+    );
+    push_present(
+        &mut record,
+        "user_agent",
+        get(&["http_user_agent", "user_agent"]).as_deref(),
+    );
+    Ok(record)
+}
+
+fn take_quoted(input: &str) -> Option<(String, &str)> {
+    let rest = input.strip_prefix('"')?;
+    let mut value = String::new();
+    let mut chars = rest.char_indices();
+    while let Some((i, ch)) = chars.next() {
+        match ch {
+            '\\' => {
+                if let Some((_, escaped)) = chars.next() {
+                    value.push(escaped);
+                }
+            }
+            '"' => return Some((value, &rest[i + 1..])),
+            _ => value.push(ch),
+        }
+    }
+    None
+}
+
+fn split_request_line(request: &str) -> Result<(&str, &str), String> {
+    let mut parts = request.split_whitespace();
+    match (parts.next(), parts.next()) {
+        (Some(method), Some(target)) => Ok((method, target)),
+        _ => Err(format!("malformed request line: {request:?}")),
+    }
+}
+
+fn build_url(
+    scheme: &str,
+    host: Option<&str>,
+    port: Option<&str>,
+    target: &str,
+) -> Result<String, String> {
+    if target.contains("://") {
+        return Ok(target.to_string());
+    }
+
+    let host = host.ok_or_else(|| {
+        "no host for relative request target (use --log-host or a vhost field)".to_string()
+    })?;
+    let default_port = match scheme {
+        "https" => "443",
+        _ => "80",
+    };
+    let authority = match port {
+        Some(port) if port != default_port && !host.contains(':') => format!("{host}:{port}"),
+        _ => host.to_string(),
+    };
+    let target = if target.starts_with('/') {
+        target.to_string()
+    } else {
+        format!("/{target}")
+    };
+    Ok(format!("{scheme}://{authority}{target}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn defaults(host: Option<&str>) -> LogDefaults {
+        LogDefaults {
+            host: host.map(str::to_string),
+            scheme: "http".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_common_log_format() {
+        let line = r#"127.0.0.1 - frank [10/Oct/2000:13:55:36 -0700] "GET /apache_pb.gif?a=1 HTTP/1.0" 200 2326"#;
+        let record = parse_clf_line(line, &defaults(Some("example.com"))).unwrap();
+
+        assert_eq!(record.url, "http://example.com/apache_pb.gif?a=1");
+        assert_eq!(record.field("method"), Some("GET"));
+        assert_eq!(record.field("status"), Some("200"));
+        assert_eq!(
+            record.field("timestamp"),
+            Some("10/Oct/2000:13:55:36 -0700")
+        );
+        assert_eq!(record.field("remote_addr"), Some("127.0.0.1"));
+        assert_eq!(record.field("bytes"), Some("2326"));
+        assert_eq!(record.field("referrer"), None);
+    }
+
+    #[test]
+    fn test_parse_combined_with_vhost() {
+        let line = r#"www.example.com:443 10.0.0.1 - - [01/Jan/2024:00:00:01 +0000] "POST /login HTTP/1.1" 302 - "https://www.example.com/" "Mozilla/5.0 (X11; \"quoted\")""#;
+        let record = parse_clf_line(line, &defaults(None)).unwrap();
+
+        assert_eq!(record.url, "https://www.example.com/login");
+        assert_eq!(record.field("method"), Some("POST"));
+        assert_eq!(record.field("status"), Some("302"));
+        assert_eq!(record.field("bytes"), None);
+        assert_eq!(record.field("referrer"), Some("https://www.example.com/"));
+        assert_eq!(
+            record.field("user_agent"),
+            Some("Mozilla/5.0 (X11; \"quoted\")")
+        );
+    }
+
+    #[test]
+    fn test_parse_clf_errors() {
+        assert!(parse_clf_line("garbage", &defaults(Some("a.com"))).is_err());
+        let line = r#"1.2.3.4 - - [01/Jan/2024:00:00:01 +0000] "GET /x HTTP/1.1" 200 5"#;
+        let err = parse_clf_line(line, &defaults(None)).unwrap_err();
+        assert!(err.contains("--log-host"));
+    }
+
+    #[test]
+    fn test_parse_proxy_absolute_target() {
+        let line = r#"1.2.3.4 - - [01/Jan/2024:00:00:01 +0000] "GET http://upstream.example/x HTTP/1.1" 200 5"#;
+        let record = parse_clf_line(line, &defaults(None)).unwrap();
+        assert_eq!(record.url, "http://upstream.example/x");
+    }
+
+    #[test]
+    fn test_parse_nginx_json() {
+        let line = r#"{"time_iso8601":"2024-01-01T00:00:00+00:00","remote_addr":"10.0.0.2","request":"GET /api/v1?x=1 HTTP/2.0","status":"404","http_host":"api.example.com","scheme":"https","http_referer":"-","http_user_agent":"curl/8.0"}"#;
+        let record = parse_nginx_json_line(line, &defaults(None)).unwrap();
+
+        assert_eq!(record.url, "https://api.example.com/api/v1?x=1");
+        assert_eq!(record.field("method"), Some("GET"));
+        assert_eq!(record.field("status"), Some("404"));
+        assert_eq!(record.field("timestamp"), Some("2024-01-01T00:00:00+00:00"));
+        assert_eq!(record.field("referrer"), None);
+        assert_eq!(record.field("user_agent"), Some("curl/8.0"));
+    }
+
+    #[test]
+    fn test_parse_nginx_json_split_uri() {
+        let line = r#"{"request_method":"HEAD","uri":"/health","args":"full=1","status":200,"host":"svc.internal"}"#;
+        let record = parse_nginx_json_line(line, &defaults(None)).unwrap();
+        assert_eq!(record.url, "http://svc.internal/health?full=1");
+        assert_eq!(record.field("method"), Some("HEAD"));
+        assert_eq!(record.field("status"), Some("200"));
+
+        assert!(parse_nginx_json_line("[1,2]", &defaults(None)).is_err());
+        assert!(parse_nginx_json_line("{not json", &defaults(None)).is_err());
+    }
+}
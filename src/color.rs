@@ -0,0 +1,225 @@
+use std::io::IsTerminal;
+
+use crate::config::ColorMode;
+
+/// Environment variable holding a user palette, in the same
+/// `component=SGR:component=SGR` form as `--colors`.
+pub const COLORS_ENV: &str = "REXTURL_COLORS";
+
+/// The parts of a URL that are highlighted, plus the marker printed for
+/// missing fields.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Component {
+    Scheme,
+    Userinfo,
+    Host,
+    Port,
+    Path,
+    Query,
+    Fragment,
+    Null,
+}
+
+impl Component {
+    const ALL: [(&'static str, Component); 8] = [
+        ("scheme", Component::Scheme),
+        ("userinfo", Component::Userinfo),
+        ("host", Component::Host),
+        ("port", Component::Port),
+        ("path", Component::Path),
+        ("query", Component::Query),
+        ("fragment", Component::Fragment),
+        ("null", Component::Null),
+    ];
+
+    /// The component an output field shows, if it is highlighted at all.
+    pub fn for_field(field: &str) -> Option<Component> {
+        match field {
+            "scheme" => Some(Component::Scheme),
+            "username" | "password" => Some(Component::Userinfo),
+            "host" | "hostname" | "subdomain" | "domain" | "unicode_host" => Some(Component::Host),
+            "port" => Some(Component::Port),
+            "path" => Some(Component::Path),
+            "query" => Some(Component::Query),
+            "fragment" => Some(Component::Fragment),
+            _ => None,
+        }
+    }
+}
+
+/// ANSI SGR parameters (such as `1;34` for bold blue) for each component.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Palette {
+    codes: [String; 8],
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            codes: ["36", "31", "1;34", "33", "32", "35", "90", "2"].map(String::from),
+        }
+    }
+}
+
+impl Palette {
+    /// Applies a `component=SGR` list separated by `:` on top of the current
+    /// palette, e.g. `host=1;32:query=33`. An empty code turns highlighting
+    /// off for that component.
+    pub fn apply(&mut self, spec: &str) -> Result<(), String> {
+        for entry in spec.split(':').filter(|entry| !entry.trim().is_empty()) {
+            let (name, code) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("expected component=code, found {entry:?}"))?;
+            let index = Component::ALL
+                .iter()
+                .position(|(component, _)| *component == name.trim())
+                .ok_or_else(|| {
+                    let names: Vec<&str> = Component::ALL.iter().map(|(name, _)| *name).collect();
+                    format!(
+                        "unknown component {:?} (components: {})",
+                        name.trim(),
+                        names.join(", ")
+                    )
+                })?;
+            let code = code.trim();
+            if !code.chars().all(|c| c.is_ascii_digit() || c == ';') {
+                return Err(format!("invalid color code {code:?} for {name}"));
+            }
+            self.codes[index] = code.to_string();
+        }
+        Ok(())
+    }
+
+    pub fn paint(&self, component: Component, text: &str) -> String {
+        let index = Component::ALL
+            .iter()
+            .position(|(_, c)| *c == component)
+            .unwrap_or_default();
+        let code = &self.codes[index];
+        if code.is_empty() || text.is_empty() {
+            text.to_string()
+        } else {
+            format!("\x1b[{code}m{text}\x1b[0m")
+        }
+    }
+
+    /// Highlights each component of a URL in place, leaving the delimiters
+    /// between them uncolored. Text that does not look like an absolute URL
+    /// is returned unchanged.
+    pub fn highlight_url(&self, url: &str) -> String {
+        let Some((scheme, rest)) = url.split_once("://") else {
+            return url.to_string();
+        };
+        let authority_end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+        let (authority, rest) = rest.split_at(authority_end);
+
+        let mut out = self.paint(Component::Scheme, scheme);
+        out.push_str("://");
+
+        let host_port = match authority.rsplit_once('@') {
+            Some((userinfo, host_port)) => {
+                out.push_str(&self.paint(Component::Userinfo, userinfo));
+                out.push('@');
+                host_port
+            }
+            None => authority,
+        };
+        let port_start = match host_port.rfind(':') {
+            Some(colon) if !host_port[colon..].contains(']') => Some(colon),
+            _ => None,
+        };
+        match port_start {
+            Some(colon) => {
+                out.push_str(&self.paint(Component::Host, &host_port[..colon]));
+                out.push(':');
+                out.push_str(&self.paint(Component::Port, &host_port[colon + 1..]));
+            }
+            None => out.push_str(&self.paint(Component::Host, host_port)),
+        }
+
+        let (before_fragment, fragment) = match rest.split_once('#') {
+            Some((before, fragment)) => (before, Some(fragment)),
+            None => (rest, None),
+        };
+        let (path, query) = match before_fragment.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (before_fragment, None),
+        };
+        out.push_str(&self.paint(Component::Path, path));
+        if let Some(query) = query {
+            out.push_str(&self.paint(Component::Query, &format!("?{query}")));
+        }
+        if let Some(fragment) = fragment {
+            out.push_str(&self.paint(Component::Fragment, &format!("#{fragment}")));
+        }
+        out
+    }
+
+    /// Highlights an output field: URLs component by component, component
+    /// fields in their component's color and anything else unchanged.
+    pub fn highlight_field(&self, field: &str, value: &str) -> String {
+        match field {
+            "url" => self.highlight_url(value),
+            _ => match Component::for_field(field) {
+                Some(component) => self.paint(component, value),
+                None => value.to_string(),
+            },
+        }
+    }
+}
+
+/// Decides whether to color output. `auto` colors only when stdout is a
+/// terminal and `NO_COLOR` is unset or empty; `always` and `never` override
+/// both.
+pub fn use_color(mode: ColorMode) -> bool {
+    match mode {
+        ColorMode::Always => true,
+        ColorMode::Never => false,
+        ColorMode::Auto => {
+            let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+            !no_color && std::io::stdout().is_terminal()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight_url() {
+        let palette = Palette::default();
+        assert_eq!(
+            palette.highlight_url("https://user@[::1]:8080/a?b=1#c"),
+            "\x1b[36mhttps\x1b[0m://\x1b[31muser\x1b[0m@\x1b[1;34m[::1]\x1b[0m:\
+             \x1b[33m8080\x1b[0m\x1b[32m/a\x1b[0m\x1b[35m?b=1\x1b[0m\x1b[90m#c\x1b[0m"
+        );
+        assert_eq!(
+            palette.highlight_url("http://example.com"),
+            "\x1b[36mhttp\x1b[0m://\x1b[1;34mexample.com\x1b[0m"
+        );
+        assert_eq!(palette.highlight_url("not a url"), "not a url");
+    }
+
+    #[test]
+    fn test_palette_apply() {
+        let mut palette = Palette::default();
+        palette.apply("host=1;32:query=").unwrap();
+        assert_eq!(
+            palette.highlight_field("hostname", "example.com"),
+            "\x1b[1;32mexample.com\x1b[0m"
+        );
+        assert_eq!(palette.highlight_field("query", "?a=1"), "?a=1");
+        assert_eq!(palette.highlight_field("status", "200"), "200");
+
+        assert!(palette.apply("hostname=32").is_err());
+        assert!(palette.apply("host=red").is_err());
+        assert!(palette.apply("host").is_err());
+    }
+
+    #[test]
+    fn test_use_color_overrides() {
+        assert!(use_color(ColorMode::Always));
+        assert!(!use_color(ColorMode::Never));
+    }
+}
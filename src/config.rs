@@ -1,27 +1,95 @@
-use clap::{Parser, ValueEnum, ValueHint};
+use clap::{Parser, Subcommand, ValueEnum, ValueHint};
 use std::io::IsTerminal;
+use std::path::PathBuf;
 
 use crate::error::AppError;
-use crate::formatter::{EscapeMode, Format, SqlDialect};
+use crate::extract::ExtractMode;
+use crate::formatter::{EscapeMode, Format, SqlDialect, TableStyle};
+use crate::input::InputFormat;
 
-#[derive(Debug, Clone, Copy, ValueEnum)]
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
 pub enum ColorMode {
+    #[default]
     Auto,
     Never,
     Always,
 }
 
-impl Default for ColorMode {
-    fn default() -> Self {
-        ColorMode::Auto
-    }
+#[derive(Debug, Subcommand, Clone)]
+pub enum Command {
+    /// Print an annotated breakdown of each URL's components
+    Explain {
+        #[arg(required = true, help = "URLs to explain")]
+        urls: Vec<String>,
+    },
 }
 
 #[derive(Debug, Parser, Clone)]
 #[command(author, version, about = "A tool for parsing and manipulating URLs", long_about = None)]
 pub struct Config {
+    #[command(subcommand)]
+    pub command: Option<Command>,
     #[arg(long, value_hint = ValueHint::AnyPath, num_args = 1.., help = "Input URLs to process")]
     pub urls: Vec<String>,
+    #[arg(
+        value_hint = ValueHint::FilePath,
+        help = "Input files or glob patterns ('-' for stdin); gzip, zstd and bzip2 files are decompressed. Use '-- explain' to read a file named explain"
+    )]
+    pub files: Vec<String>,
+    #[arg(
+        long,
+        value_hint = ValueHint::FilePath,
+        help = "Input file or glob pattern, may be repeated ('-' for stdin)"
+    )]
+    pub input: Vec<String>,
+    #[arg(
+        long,
+        value_enum,
+        num_args = 0..=1,
+        default_missing_value = "text",
+        help = "Scan input as free-form text, HTML, Markdown or JavaScript and extract the URLs it contains"
+    )]
+    pub extract: Option<ExtractMode>,
+    #[arg(
+        long,
+        help = "Base URL for resolving relative links found by --extract and robots.txt rules"
+    )]
+    pub base: Option<String>,
+    #[arg(
+        long,
+        value_enum,
+        default_value = "lines",
+        help = "Input format: one URL per line, web server access logs, HAR/Burp traffic exports, CSV/JSONL records, sitemap.xml/robots.txt files, WARC archives, rexturl's own JSON/JSONL output, or component columns to assemble into URLs"
+    )]
+    pub input_format: InputFormat,
+    #[arg(
+        long,
+        help = "Host for access log requests without a vhost or Host field"
+    )]
+    pub log_host: Option<String>,
+    #[arg(
+        long,
+        default_value = "http",
+        help = "Scheme for access log requests that do not record one"
+    )]
+    pub log_scheme: String,
+    #[arg(
+        long,
+        default_value = "url",
+        help = "CSV column holding the URL, by header name or 1-based number"
+    )]
+    pub url_column: String,
+    #[arg(
+        long,
+        default_value = "url",
+        help = "JSONL field holding the URL, as a dotted path (e.g., request.url)"
+    )]
+    pub url_field: String,
+    #[arg(
+        long,
+        help = "Component columns for --input-format assemble, as component=column pairs (e.g., host=server,path=uri)"
+    )]
+    pub component_columns: Option<String>,
 
     #[arg(long, help = "Extract and display the URL scheme")]
     pub scheme: bool,
@@ -49,15 +117,65 @@ pub struct Config {
     pub fields: Option<String>,
     #[arg(long, help = "Include header row for tabular formats")]
     pub header: bool,
-    #[arg(long, help = "Pretty-print JSON output")]
+    #[arg(
+        long,
+        default_value = ",",
+        help = "Field delimiter for CSV output and assemble input (e.g., ';' for European spreadsheets)"
+    )]
+    pub csv_delimiter: char,
+    #[arg(
+        long,
+        default_value = "\"",
+        help = "Quote character for CSV output and assemble input"
+    )]
+    pub csv_quote: char,
+    #[arg(
+        long,
+        help = "Start CSV and TSV output with a UTF-8 byte order mark for Excel"
+    )]
+    pub bom: bool,
+    #[arg(
+        long,
+        value_enum,
+        default_value = "unicode",
+        help = "Border style for table output"
+    )]
+    pub table_style: TableStyle,
+    #[arg(
+        long,
+        default_value_t = 40,
+        value_name = "CHARS",
+        help = "Truncate table cells longer than this many characters (0 for no limit)"
+    )]
+    pub max_width: usize,
+    #[arg(long, help = "Group HTML output into one table per domain")]
+    pub group_by_domain: bool,
+    #[arg(long, help = "Pretty-print JSON, XML and YAML output")]
     pub pretty: bool,
+    #[arg(
+        long,
+        help = "Write typed JSON values: numeric ports, query pairs, path segment arrays and explicit nulls"
+    )]
+    pub typed: bool,
+    #[arg(
+        long,
+        help = "Print the JSON Schema of the records --format json/jsonl would write, and exit"
+    )]
+    pub json_schema: bool,
     #[arg(
         long,
         value_enum,
         default_value = "auto",
-        help = "When to use colored output (plain format only)"
+        global = true,
+        help = "When to color output (plain format and explain)"
     )]
     pub color: ColorMode,
+    #[arg(
+        long,
+        global = true,
+        help = "Color palette as component=SGR pairs separated by ':' (e.g., host=1;32:query=33); overrides REXTURL_COLORS"
+    )]
+    pub colors: Option<String>,
     #[arg(
         long,
         default_value = "\\N",
@@ -73,6 +191,41 @@ pub struct Config {
     pub sort: bool,
     #[arg(long, help = "Remove duplicate entries from the output")]
     pub unique: bool,
+    #[arg(
+        long,
+        help = "Keep only records matching an expression (e.g., 'domain == \"example.com\" && path ~ \"^/api/\"')"
+    )]
+    pub filter: Option<String>,
+
+    #[arg(
+        long,
+        help = "Score URLs with phishing heuristics (adds risk_score and risk_reasons fields)"
+    )]
+    pub risk: bool,
+    #[arg(
+        long,
+        value_hint = ValueHint::FilePath,
+        help = "File of brand domains; hosts whose confusable skeleton matches one are flagged in homograph_of"
+    )]
+    pub protect_list: Option<PathBuf>,
+    #[arg(
+        long,
+        value_hint = ValueHint::FilePath,
+        help = "File of watched domains; reports the closest typosquat match in similar_to, mutation and edit_distance"
+    )]
+    pub similar_to: Option<PathBuf>,
+    #[arg(
+        long,
+        value_hint = ValueHint::FilePath,
+        help = "Scope file of in-scope and !out-of-scope hosts, CIDR ranges, ports and paths; drops out-of-scope URLs"
+    )]
+    pub scope: Option<PathBuf>,
+    #[arg(
+        long,
+        requires = "scope",
+        help = "Keep out-of-scope URLs and report scope membership in the in_scope field"
+    )]
+    pub scope_annotate: bool,
 
     #[arg(
         long,
@@ -91,8 +244,20 @@ pub struct Config {
     pub sql_table: String,
     #[arg(long, help = "Include CREATE TABLE statement in SQL output")]
     pub sql_create_table: bool,
+    #[arg(
+        long,
+        help = "Index the domain and hostname columns in CREATE TABLE output"
+    )]
+    pub sql_indexes: bool,
     #[arg(long, value_enum, default_value = "postgres", help = "SQL dialect")]
     pub sql_dialect: SqlDialect,
+    #[arg(
+        long,
+        default_value_t = 1000,
+        value_name = "ROWS",
+        help = "Rows per INSERT statement in SQL output"
+    )]
+    pub sql_batch_size: usize,
 
     #[arg(
         long,
@@ -117,8 +282,14 @@ impl Config {
 }
 
 pub fn check_for_stdin() -> Result<(), AppError> {
-    if std::io::stdin().is_terminal() && Config::from_args().urls.is_empty() {
-        eprintln!("Error: No input URLs provided. Use --urls or pipe input from stdin.");
+    let config = Config::from_args();
+    if std::io::stdin().is_terminal()
+        && config.command.is_none()
+        && config.urls.is_empty()
+        && config.files.is_empty()
+        && config.input.is_empty()
+    {
+        eprintln!("Error: No input URLs provided. Use --urls, pass input files, or pipe input from stdin.");
         std::process::exit(1);
     }
     Ok(())
@@ -0,0 +1,252 @@
+use serde_json::Value;
+
+use crate::formatter::{json_object, print_rendered, UrlRecord};
+
+/// Renders records as an XML document:
+///
+/// ```xml
+/// <?xml version="1.0" encoding="UTF-8"?>
+/// <urls count="1">
+///   <record>
+///     <domain>example.com</domain>
+///     <path>/a</path>
+///   </record>
+/// </urls>
+/// ```
+///
+/// Each selected field becomes an element named after it, in `--fields`
+/// order, and missing fields are left out. Fields whose names are not valid
+/// XML names, such as CSV columns with spaces, are written as
+/// `<field name="...">` instead. Without `pretty` the document is a single
+/// line.
+pub fn render_xml(records: &[UrlRecord], fields: &[&str], pretty: bool) -> String {
+    let (newline, indent) = if pretty { ("\n", "  ") } else { ("", "") };
+    let mut out = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>{newline}<urls count=\"{}\">{newline}",
+        records.len()
+    );
+    for record in records {
+        out.push_str(&format!("{indent}<record>{newline}"));
+        for field in fields {
+            let Some(value) = record.get_field(field) else {
+                continue;
+            };
+            let value = xml_escape(value);
+            if is_xml_name(field) {
+                out.push_str(&format!(
+                    "{indent}{indent}<{field}>{value}</{field}>{newline}"
+                ));
+            } else {
+                out.push_str(&format!(
+                    "{indent}{indent}<field name=\"{}\">{value}</field>{newline}",
+                    xml_escape(field)
+                ));
+            }
+        }
+        out.push_str(&format!("{indent}</record>{newline}"));
+    }
+    out.push_str("</urls>\n");
+    out
+}
+
+pub fn print_xml(records: &[UrlRecord], fields: &[&str], pretty: bool, no_newline: bool) {
+    print_rendered(&render_xml(records, fields, pretty), no_newline);
+}
+
+/// Escapes text for XML element content and attribute values. Control
+/// characters that XML 1.0 does not allow, even as references, become
+/// U+FFFD.
+pub fn xml_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() && c < ' ' => escaped.push('\u{FFFD}'),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Whether a field name can be used as an element name as is. Names
+/// starting with `xml` are reserved.
+fn is_xml_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        && !name.to_ascii_lowercase().starts_with("xml")
+}
+
+/// Renders records as a YAML document with a `urls` list, mirroring
+/// `--format json` but keeping fields in `--fields` order. With `pretty` each
+/// record is a block mapping; otherwise each record is a flow mapping on one
+/// line. `typed` writes the same typed values as JSON output.
+pub fn render_yaml(records: &[UrlRecord], fields: &[&str], typed: bool, pretty: bool) -> String {
+    if records.is_empty() {
+        return "urls: []\n".to_string();
+    }
+    let mut out = String::from("urls:\n");
+    for record in records {
+        let Value::Object(map) = json_object(record, fields, typed) else {
+            continue;
+        };
+        let entries: Vec<String> = fields
+            .iter()
+            .filter_map(|field| {
+                let value = map.get(*field)?;
+                Some(format!("{}: {}", yaml_scalar(field), yaml_flow(value)))
+            })
+            .collect();
+        if !pretty || entries.is_empty() {
+            out.push_str(&format!("  - {{{}}}\n", entries.join(", ")));
+            continue;
+        }
+        for (i, entry) in entries.iter().enumerate() {
+            let prefix = if i == 0 { "  - " } else { "    " };
+            out.push_str(&format!("{prefix}{entry}\n"));
+        }
+    }
+    out
+}
+
+pub fn print_yaml(
+    records: &[UrlRecord],
+    fields: &[&str],
+    typed: bool,
+    pretty: bool,
+    no_newline: bool,
+) {
+    print_rendered(&render_yaml(records, fields, typed, pretty), no_newline);
+}
+
+/// Writes a value in YAML flow style; nested values such as typed query
+/// parameters stay on one line.
+fn yaml_flow(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => yaml_scalar(s),
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(yaml_flow).collect();
+            format!("[{}]", items.join(", "))
+        }
+        Value::Object(map) => {
+            let entries: Vec<String> = map
+                .iter()
+                .map(|(key, value)| format!("{}: {}", yaml_scalar(key), yaml_flow(value)))
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+    }
+}
+
+/// Writes a string plain when it cannot be read back as anything else, and
+/// double-quoted (with JSON escapes, which YAML shares) otherwise. URLs are
+/// always quoted because of their `:`.
+fn yaml_scalar(value: &str) -> String {
+    let plain = value
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '/' || c == '_')
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | '/'))
+        && !matches!(
+            value.to_ascii_lowercase().as_str(),
+            "true" | "false" | "yes" | "no" | "on" | "off" | "y" | "n" | "null" | "nan" | "inf"
+        );
+    if plain {
+        value.to_string()
+    } else {
+        Value::String(value.to_string()).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formatter::to_record;
+
+    #[test]
+    fn test_render_xml() {
+        let mut record = to_record("https://example.com/a?x=1&y=<2>").unwrap();
+        record.set_extra("page title", "Tom & Jerry");
+        let fields = ["domain", "query", "port", "page title"];
+
+        assert_eq!(
+            render_xml(&[record.clone()], &fields, true),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <urls count=\"1\">\n  \
+               <record>\n    \
+                 <domain>example.com</domain>\n    \
+                 <query>?x=1&amp;y=&lt;2&gt;</query>\n    \
+                 <field name=\"page title\">Tom &amp; Jerry</field>\n  \
+               </record>\n\
+             </urls>\n"
+        );
+        assert_eq!(
+            render_xml(&[record], &["domain"], false),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?><urls count=\"1\">\
+             <record><domain>example.com</domain></record></urls>\n"
+        );
+    }
+
+    #[test]
+    fn test_xml_escape() {
+        assert_eq!(
+            xml_escape("a&b<c>\"'\u{1}\t"),
+            "a&amp;b&lt;c&gt;&quot;&apos;\u{FFFD}\t"
+        );
+        assert!(is_xml_name("risk_score"));
+        assert!(!is_xml_name("page title"));
+        assert!(!is_xml_name("1st"));
+        assert!(!is_xml_name("xmlns"));
+    }
+
+    #[test]
+    fn test_render_yaml() {
+        let records = [
+            to_record("https://example.com:8080/a/b?q=1&q=2").unwrap(),
+            to_record("http://example.org/").unwrap(),
+        ];
+        let fields = ["url", "domain", "port", "path"];
+
+        assert_eq!(
+            render_yaml(&records, &fields, false, true),
+            "urls:\n  \
+               - url: \"https://example.com:8080/a/b?q=1&q=2\"\n    \
+                 domain: example.com\n    \
+                 port: \"8080\"\n    \
+                 path: /a/b\n  \
+               - url: \"http://example.org/\"\n    \
+                 domain: example.org\n    \
+                 path: /\n"
+        );
+        assert_eq!(
+            render_yaml(&records[1..], &["domain", "port", "path"], true, false),
+            "urls:\n  - {domain: example.org, port: null, path: [\"\"]}\n"
+        );
+        assert_eq!(
+            render_yaml(&records[..1], &["port", "query"], true, true),
+            "urls:\n  - port: 8080\n    query: [[q, \"1\"], [q, \"2\"]]\n"
+        );
+        assert_eq!(render_yaml(&[], &fields, false, true), "urls: []\n");
+    }
+
+    #[test]
+    fn test_yaml_scalar() {
+        assert_eq!(yaml_scalar("example.com"), "example.com");
+        assert_eq!(yaml_scalar("no"), "\"no\"");
+        assert_eq!(yaml_scalar("443"), "\"443\"");
+        assert_eq!(yaml_scalar(""), "\"\"");
+        assert_eq!(yaml_scalar("a: b\n"), "\"a: b\\n\"");
+    }
+}
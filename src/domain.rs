@@ -1,3 +1,10 @@
+use std::fs;
+use std::path::Path;
+
+use unicode_security::{skeleton, MixedScript};
+
+use crate::error::AppError;
+
 pub const MULTI_PART_TLDS: &[&str] = &[
     "co.uk", "org.uk", "ac.uk", "gov.uk", "me.uk", "net.uk", "sch.uk", "com.au", "net.au",
     "org.au", "edu.au", "gov.au", "co.nz", "net.nz", "org.nz", "govt.nz", "co.za", "org.za",
@@ -5,12 +12,46 @@ pub const MULTI_PART_TLDS: &[&str] = &[
     "com.hk", "co.th", "in.th",
 ];
 
+pub const URL_SHORTENERS: &[&str] = &[
+    "bit.ly",
+    "t.co",
+    "tinyurl.com",
+    "goo.gl",
+    "ow.ly",
+    "is.gd",
+    "buff.ly",
+    "rebrand.ly",
+    "cutt.ly",
+    "shorturl.at",
+    "tiny.cc",
+    "rb.gy",
+    "t.ly",
+    "s.id",
+    "lnkd.in",
+    "bl.ink",
+    "v.gd",
+    "adf.ly",
+    "soo.gd",
+    "shorte.st",
+];
+
 pub fn is_multi_part_tld(domain: &str) -> bool {
     MULTI_PART_TLDS
         .iter()
         .any(|tld| domain.ends_with(&format!(".{tld}")))
 }
 
+/// Reads a domain list file: one domain per line, blank lines and `#` comments ignored.
+pub fn read_domain_list(path: &Path) -> Result<Vec<String>, AppError> {
+    let contents = fs::read_to_string(path)?;
+    Ok(contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect())
+}
+
 pub fn extract_domain(host: &str) -> String {
     if host.starts_with('[') && host.ends_with(']') {
         return String::new();
@@ -35,6 +76,146 @@ pub fn extract_domain(host: &str) -> String {
     parts[(parts_len - 2)..].join(".")
 }
 
+pub fn is_url_shortener(domain: &str) -> bool {
+    URL_SHORTENERS
+        .iter()
+        .any(|shortener| domain.eq_ignore_ascii_case(shortener))
+}
+
+pub fn is_ip_literal(host: &str) -> bool {
+    if host.starts_with('[') && host.ends_with(']') {
+        return true;
+    }
+
+    if host.parse::<std::net::Ipv4Addr>().is_ok() {
+        return true;
+    }
+
+    // Browsers also resolve single-integer and hex hosts such as 3232235777 or 0xc0a80001.
+    let digits = host
+        .strip_prefix("0x")
+        .or_else(|| host.strip_prefix("0X"))
+        .map(|hex| (hex, 16))
+        .unwrap_or((host, 10));
+    !digits.0.is_empty() && u32::from_str_radix(digits.0, digits.1).is_ok()
+}
+
+pub fn is_punycode(host: &str) -> bool {
+    host.split('.').any(|label| {
+        label.len() > 4
+            && label
+                .get(..4)
+                .is_some_and(|p| p.eq_ignore_ascii_case("xn--"))
+    })
+}
+
+const PUNYCODE_BASE: u32 = 36;
+const PUNYCODE_TMIN: u32 = 1;
+const PUNYCODE_TMAX: u32 = 26;
+const PUNYCODE_SKEW: u32 = 38;
+const PUNYCODE_DAMP: u32 = 700;
+const PUNYCODE_INITIAL_BIAS: u32 = 72;
+const PUNYCODE_INITIAL_N: u32 = 128;
+
+/// Decodes a single punycode label (without the `xn--` prefix) as described in RFC 3492.
+pub fn decode_punycode(input: &str) -> Option<String> {
+    let (basic, encoded) = match input.rfind('-') {
+        Some(pos) => (&input[..pos], &input[pos + 1..]),
+        None => ("", input),
+    };
+    if !basic.is_ascii() {
+        return None;
+    }
+
+    let mut output: Vec<char> = basic.chars().collect();
+    let mut n = PUNYCODE_INITIAL_N;
+    let mut bias = PUNYCODE_INITIAL_BIAS;
+    let mut i: u32 = 0;
+    let mut bytes = encoded.bytes().peekable();
+
+    while bytes.peek().is_some() {
+        let old_i = i;
+        let mut w: u32 = 1;
+        let mut k = PUNYCODE_BASE;
+        loop {
+            let digit = match bytes.next()? {
+                b @ b'a'..=b'z' => (b - b'a') as u32,
+                b @ b'A'..=b'Z' => (b - b'A') as u32,
+                b @ b'0'..=b'9' => (b - b'0') as u32 + 26,
+                _ => return None,
+            };
+            i = i.checked_add(digit.checked_mul(w)?)?;
+            let t = if k <= bias {
+                PUNYCODE_TMIN
+            } else if k >= bias + PUNYCODE_TMAX {
+                PUNYCODE_TMAX
+            } else {
+                k - bias
+            };
+            if digit < t {
+                break;
+            }
+            w = w.checked_mul(PUNYCODE_BASE - t)?;
+            k += PUNYCODE_BASE;
+        }
+
+        let len = output.len() as u32 + 1;
+        bias = punycode_adapt(i - old_i, len, old_i == 0);
+        n = n.checked_add(i / len)?;
+        i %= len;
+        output.insert(i as usize, char::from_u32(n)?);
+        i += 1;
+    }
+
+    Some(output.into_iter().collect())
+}
+
+fn punycode_adapt(delta: u32, num_points: u32, first_time: bool) -> u32 {
+    let mut delta = if first_time {
+        delta / PUNYCODE_DAMP
+    } else {
+        delta / 2
+    };
+    delta += delta / num_points;
+
+    let mut k = 0;
+    while delta > ((PUNYCODE_BASE - PUNYCODE_TMIN) * PUNYCODE_TMAX) / 2 {
+        delta /= PUNYCODE_BASE - PUNYCODE_TMIN;
+        k += PUNYCODE_BASE;
+    }
+    k + ((PUNYCODE_BASE - PUNYCODE_TMIN + 1) * delta) / (delta + PUNYCODE_SKEW)
+}
+
+/// Lowercases the host and decodes any `xn--` labels to Unicode.
+pub fn to_unicode_host(host: &str) -> String {
+    host.split('.')
+        .map(|label| {
+            let lower = label.to_lowercase();
+            lower
+                .strip_prefix("xn--")
+                .and_then(decode_punycode)
+                .unwrap_or(lower)
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// True when any label of the (decoded) host mixes characters from different scripts.
+pub fn is_mixed_script(host: &str) -> bool {
+    to_unicode_host(host)
+        .split('.')
+        .any(|label| !label.is_ascii() && !label.is_single_script())
+}
+
+/// Computes the UTS #39 confusable skeleton of each host label.
+pub fn host_skeleton(host: &str) -> String {
+    to_unicode_host(host)
+        .split('.')
+        .map(|label| skeleton(label).collect::<String>())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
 pub fn extract_subdomain(host: &str) -> String {
     let domain = extract_domain(host);
 
@@ -75,6 +256,50 @@ mod tests {
         assert_eq!(extract_subdomain("blog.dev.example.com"), "blog.dev");
     }
 
+    #[test]
+    fn test_is_ip_literal() {
+        assert!(is_ip_literal("192.168.0.1"));
+        assert!(is_ip_literal("[::1]"));
+        assert!(is_ip_literal("3232235521"));
+        assert!(is_ip_literal("0xc0a80001"));
+        assert!(!is_ip_literal("example.com"));
+        assert!(!is_ip_literal("1.example.com"));
+    }
+
+    #[test]
+    fn test_is_punycode_and_shortener() {
+        assert!(is_punycode("xn--pypal-4ve.com"));
+        assert!(is_punycode("www.XN--80ak6aa92e.com"));
+        assert!(!is_punycode("example.com"));
+        assert!(is_url_shortener("bit.ly"));
+        assert!(!is_url_shortener("example.com"));
+    }
+
+    #[test]
+    fn test_decode_punycode() {
+        assert_eq!(
+            decode_punycode("pypal-4ve").as_deref(),
+            Some("p\u{430}ypal")
+        );
+        assert_eq!(decode_punycode("mnchen-3ya").as_deref(), Some("münchen"));
+        assert_eq!(decode_punycode("r8jz45g").as_deref(), Some("例え"));
+        assert_eq!(decode_punycode("bad!"), None);
+        assert_eq!(to_unicode_host("www.XN--bcher-kva.de"), "www.bücher.de");
+    }
+
+    #[test]
+    fn test_mixed_script_and_skeleton() {
+        assert!(is_mixed_script("xn--ypal-43d9g.com"));
+        assert!(is_mixed_script("p\u{430}ypal.com"));
+        assert!(!is_mixed_script("bücher.de"));
+        assert!(!is_mixed_script("example.com"));
+        assert_eq!(
+            host_skeleton("\u{440}\u{430}ypal.com"),
+            host_skeleton("paypal.com")
+        );
+        assert_ne!(host_skeleton("example.com"), host_skeleton("paypal.com"));
+    }
+
     #[test]
     fn test_extract_subdomain_multipart_tld() {
         assert_eq!(extract_subdomain("example.co.uk"), "");
@@ -1,6 +1,7 @@
 use std::fmt;
 use std::io;
 
+use crate::scope::ScopeParseError;
 use crate::url::UrlParseError;
 
 #[derive(Debug)]
@@ -8,6 +9,7 @@ pub enum AppError {
     IoError(io::Error),
     UrlParseError(UrlParseError),
     JsonError(serde_json::Error),
+    ScopeError(ScopeParseError),
 }
 
 impl fmt::Display for AppError {
@@ -16,6 +18,7 @@ impl fmt::Display for AppError {
             AppError::IoError(err) => write!(f, "IO error: {err}"),
             AppError::UrlParseError(err) => write!(f, "URL parse error: {err}"),
             AppError::JsonError(err) => write!(f, "JSON error: {err}"),
+            AppError::ScopeError(err) => write!(f, "Scope error: {err}"),
         }
     }
 }
@@ -37,3 +40,9 @@ impl From<serde_json::Error> for AppError {
         AppError::JsonError(err)
     }
 }
+
+impl From<ScopeParseError> for AppError {
+    fn from(err: ScopeParseError) -> Self {
+        AppError::ScopeError(err)
+    }
+}
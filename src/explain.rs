@@ -0,0 +1,340 @@
+use std::fmt::Write;
+
+use crate::color::{Component, Palette};
+use crate::domain::{
+    extract_domain, extract_subdomain, is_ip_literal, is_mixed_script, is_punycode, to_unicode_host,
+};
+use crate::parser::default_port;
+use crate::url::{Url, UrlParseError, UrlPart};
+
+const LABEL_WIDTH: usize = 14;
+
+/// Describes a URL for `rexturl explain`: the URL with each component marked
+/// underneath it, then the components with their decoded values, the host's
+/// type and registrable domain, the effective port and any warnings about
+/// the way the URL is written. Marker rows and the URL are colored with
+/// `palette` when given.
+pub fn explain(input: &str, palette: Option<&Palette>) -> Result<String, UrlParseError> {
+    let url = Url::parse(input)?;
+    let text = url.as_str();
+    let ranges = url.component_ranges();
+    let mut out = String::new();
+
+    let shown = match palette {
+        Some(palette) => palette.highlight_url(text),
+        None => text.to_string(),
+    };
+    let _ = writeln!(out, "{:LABEL_WIDTH$}{shown}", "");
+    for (part, range) in &ranges {
+        let start = text[..range.start()].chars().count();
+        let width = text[range.start()..range.end()].chars().count();
+        let marker = "^".repeat(width);
+        let marker = match palette {
+            Some(palette) => palette.paint(component(*part), &marker),
+            None => marker,
+        };
+        let _ = writeln!(
+            out,
+            "{:LABEL_WIDTH$}{}{marker}",
+            part.as_str(),
+            " ".repeat(start)
+        );
+    }
+    out.push('\n');
+
+    let scheme = url.scheme().to_ascii_lowercase();
+    let host = url.host();
+    let _ = writeln!(out, "{:LABEL_WIDTH$}{}", "scheme", url.scheme());
+    for (label, value) in [("username", url.username()), ("password", url.password())] {
+        if !value.is_empty() {
+            line(&mut out, label, value);
+        }
+    }
+
+    let _ = writeln!(out, "{:LABEL_WIDTH$}{host}", "host");
+    let _ = writeln!(
+        out,
+        "  {:width$}{}",
+        "type",
+        host_type(host),
+        width = LABEL_WIDTH - 2
+    );
+    if is_punycode(host) {
+        let _ = writeln!(
+            out,
+            "  {:width$}{}",
+            "unicode",
+            to_unicode_host(host),
+            width = LABEL_WIDTH - 2
+        );
+    }
+    let registrable = extract_domain(host);
+    if !registrable.is_empty() && registrable.contains('.') {
+        let subdomain = extract_subdomain(host);
+        if !subdomain.is_empty() {
+            let _ = writeln!(
+                out,
+                "  {:width$}{subdomain}",
+                "subdomain",
+                width = LABEL_WIDTH - 2
+            );
+        }
+        let _ = writeln!(
+            out,
+            "  {:width$}{registrable}",
+            "registrable",
+            width = LABEL_WIDTH - 2
+        );
+        if let Some((_, suffix)) = registrable.split_once('.') {
+            let _ = writeln!(
+                out,
+                "  {:width$}{suffix}",
+                "suffix",
+                width = LABEL_WIDTH - 2
+            );
+        }
+    }
+
+    let port = match (url.port(), default_port(&scheme)) {
+        (Some(port), Some(default)) if port == default => format!("{port} (explicit default)"),
+        (Some(port), _) => port.to_string(),
+        (None, Some(default)) => format!("{default} (default for {scheme})"),
+        (None, None) => "none (no default for this scheme)".to_string(),
+    };
+    let _ = writeln!(out, "{:LABEL_WIDTH$}{port}", "port");
+
+    line(&mut out, "path", url.path());
+    if let Some(query) = url.query() {
+        let _ = writeln!(out, "{:LABEL_WIDTH$}{query}", "query");
+        for param in query.split('&').filter(|param| !param.is_empty()) {
+            let (key, value) = param.split_once('=').unwrap_or((param, ""));
+            let key = percent_decode(&key.replace('+', " "));
+            let value = percent_decode(&value.replace('+', " "));
+            let param = format!("  {key:width$}{value}", width = LABEL_WIDTH - 2);
+            let _ = writeln!(out, "{}", param.trim_end());
+        }
+    }
+    if let Some(fragment) = url.fragment() {
+        line(&mut out, "fragment", fragment);
+    }
+
+    let warnings = warnings(&url);
+    if !warnings.is_empty() {
+        out.push('\n');
+        let _ = writeln!(out, "warnings");
+        for warning in warnings {
+            let _ = writeln!(out, "  - {warning}");
+        }
+    }
+    Ok(out)
+}
+
+/// Writes a labeled value, followed by its percent-decoded form when that
+/// differs.
+fn line(out: &mut String, label: &str, value: &str) {
+    let _ = writeln!(out, "{label:LABEL_WIDTH$}{value}");
+    let decoded = percent_decode(value);
+    if decoded != value {
+        let _ = writeln!(
+            out,
+            "  {:width$}{decoded}",
+            "decoded",
+            width = LABEL_WIDTH - 2
+        );
+    }
+}
+
+fn component(part: UrlPart) -> Component {
+    match part {
+        UrlPart::Scheme => Component::Scheme,
+        UrlPart::Username | UrlPart::Password => Component::Userinfo,
+        UrlPart::Host => Component::Host,
+        UrlPart::Port => Component::Port,
+        UrlPart::Path => Component::Path,
+        UrlPart::Query => Component::Query,
+        UrlPart::Fragment => Component::Fragment,
+    }
+}
+
+fn host_type(host: &str) -> &'static str {
+    if host.starts_with('[') {
+        "IPv6 address"
+    } else if host.parse::<std::net::Ipv4Addr>().is_ok() {
+        "IPv4 address"
+    } else if is_ip_literal(host) {
+        "IPv4 address in integer or hex form"
+    } else if host.eq_ignore_ascii_case("localhost") {
+        "loopback name"
+    } else if is_punycode(host) || !host.is_ascii() {
+        "internationalized domain name"
+    } else if !host.contains('.') {
+        "single-label name"
+    } else {
+        "domain name"
+    }
+}
+
+fn warnings(url: &Url) -> Vec<String> {
+    let text = url.as_str();
+    let host = url.host();
+    let scheme = url.scheme().to_ascii_lowercase();
+    let mut warnings = Vec::new();
+
+    if !url.password().is_empty() {
+        warnings.push("password embedded in the URL".to_string());
+    } else if !url.username().is_empty() {
+        warnings.push("userinfo before the host can disguise the real host".to_string());
+    }
+    if url.scheme() != scheme {
+        warnings.push("scheme is not lowercase".to_string());
+    }
+    if host.chars().any(|c| c.is_ascii_uppercase()) {
+        warnings.push("host has uppercase letters (hosts are case-insensitive)".to_string());
+    }
+    if host.ends_with('.') {
+        warnings.push("host ends with a dot".to_string());
+    }
+    if host.contains('%') {
+        warnings.push("host contains percent-encoded characters".to_string());
+    }
+    if is_mixed_script(host) {
+        warnings.push("host mixes characters from different scripts".to_string());
+    }
+    if is_ip_literal(host) && !host.starts_with('[') && host.parse::<std::net::Ipv4Addr>().is_err()
+    {
+        warnings.push("integer or hex IPv4 host is resolved by browsers".to_string());
+    }
+    if let (Some(port), Some(default)) = (url.port(), default_port(&scheme)) {
+        if port == default {
+            warnings.push(format!("port {port} is the default for {scheme}"));
+        }
+    }
+
+    let path = url.path();
+    if path
+        .split('/')
+        .any(|segment| segment == "." || segment == "..")
+    {
+        warnings.push("path has dot segments".to_string());
+    }
+    if path.contains("//") {
+        warnings.push("path has empty segments".to_string());
+    }
+    for (part, range) in url.component_ranges() {
+        let value = &text[range.start()..range.end()];
+        if has_invalid_escape(value) {
+            warnings.push(format!(
+                "{} has a '%' that is not a valid escape",
+                part.as_str()
+            ));
+        }
+    }
+    if text.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        warnings.push("URL contains whitespace or control characters".to_string());
+    }
+    if !text.is_ascii() {
+        warnings.push("URL contains characters that are not percent-encoded".to_string());
+    }
+    if text.ends_with('?') {
+        warnings.push("empty query after '?'".to_string());
+    }
+    if text.ends_with('#') {
+        warnings.push("empty fragment after '#'".to_string());
+    }
+    warnings
+}
+
+fn has_invalid_escape(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    bytes.iter().enumerate().any(|(i, &b)| {
+        b == b'%'
+            && !(bytes.get(i + 1).is_some_and(u8::is_ascii_hexdigit)
+                && bytes.get(i + 2).is_some_and(u8::is_ascii_hexdigit))
+    })
+}
+
+/// Decodes `%XX` escapes, replacing sequences that are not valid UTF-8.
+/// Invalid escapes are kept as written.
+pub fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explain_marks_components() {
+        let text = explain(
+            "https://user@www.example.co.uk:443/a%20b/../c?q=x+y#top",
+            None,
+        )
+        .unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "              https://user@www.example.co.uk:443/a%20b/../c?q=x+y#top"
+        );
+        assert_eq!(lines[1], "scheme        ^^^^^");
+        assert_eq!(lines[2], "username              ^^^^");
+        assert_eq!(lines[3], "host                       ^^^^^^^^^^^^^^^^^");
+        assert_eq!(lines[4], "port                                         ^^^");
+
+        assert!(text.contains("\n  type        domain name\n"));
+        assert!(text.contains("\n  registrable example.co.uk\n  suffix      co.uk\n"));
+        assert!(text.contains("\nport          443 (explicit default)\n"));
+        assert!(text.contains("\n  decoded     /a b/../c\n"));
+        assert!(text.contains("\n  q           x y\n"));
+        assert!(text.contains("  - userinfo before the host can disguise the real host\n"));
+        assert!(text.contains("  - port 443 is the default for https\n"));
+        assert!(text.contains("  - path has dot segments\n"));
+    }
+
+    #[test]
+    fn test_explain_hosts() {
+        let text = explain("http://[::1]/", None).unwrap();
+        assert!(text.contains("type        IPv6 address"));
+        assert!(text.contains("port          80 (default for http)"));
+        assert!(!text.contains("warnings"));
+
+        let text = explain("http://xn--pypal-4ve.com/%zz", None).unwrap();
+        assert!(text.contains("internationalized domain name"));
+        assert!(text.contains("unicode     pаypal.com"));
+        assert!(text.contains("path has a '%' that is not a valid escape"));
+
+        assert!(explain("not a url", None).is_err());
+    }
+
+    #[test]
+    fn test_explain_colors_markers() {
+        let text = explain("http://example.com/", Some(&Palette::default())).unwrap();
+        assert!(text.contains("scheme        \x1b[36m^^^^\x1b[0m\n"));
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("a%20b%C3%A9"), "a bé");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz%4"), "%zz%4");
+    }
+}
@@ -0,0 +1,721 @@
+use clap::ValueEnum;
+
+use crate::parser::resolve_url;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum ExtractMode {
+    #[default]
+    Text,
+    Html,
+    Markdown,
+    Js,
+}
+
+/// A URL found in free-form input, with the byte offset where it starts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedUrl {
+    pub url: String,
+    pub offset: usize,
+}
+
+const HTML_URL_ATTRIBUTES: &[&str] = &["href", "src", "action", "poster", "cite", "data"];
+
+/// Extensions that mark a relative string literal such as `api/users.json`
+/// as an endpoint rather than arbitrary text containing a slash.
+const JS_ENDPOINT_EXTENSIONS: &[&str] = &[
+    ".php", ".asp", ".aspx", ".jsp", ".json", ".xml", ".html", ".htm", ".js", ".action", ".do",
+];
+
+pub fn extract_urls(mode: ExtractMode, input: &str, base: Option<&str>) -> Vec<ExtractedUrl> {
+    match mode {
+        ExtractMode::Text => extract_from_text(input),
+        ExtractMode::Html => extract_from_html(input, base),
+        ExtractMode::Markdown => extract_from_markdown(input, base),
+        ExtractMode::Js => extract_from_js(input, base),
+    }
+}
+
+/// Scans arbitrary text for `scheme://` URLs, trimming trailing punctuation,
+/// unbalanced closing brackets and surrounding quotes.
+pub fn extract_from_text(input: &str) -> Vec<ExtractedUrl> {
+    let bytes = input.as_bytes();
+    let mut found = Vec::new();
+    let mut search_from = 0;
+
+    while let Some(relative) = input[search_from..].find("://") {
+        let separator = search_from + relative;
+        let mut start = separator;
+        while start > 0 && is_scheme_byte(bytes[start - 1]) {
+            start -= 1;
+        }
+        while start < separator && !bytes[start].is_ascii_alphabetic() {
+            start += 1;
+        }
+
+        if start == separator {
+            search_from = separator + 3;
+            continue;
+        }
+
+        let opening_quote = start
+            .checked_sub(1)
+            .map(|i| bytes[i])
+            .filter(|b| matches!(b, b'\'' | b'"' | b'`'));
+        let mut end = separator + 3;
+        for (offset, ch) in input[end..].char_indices() {
+            if ch.is_whitespace()
+                || ch.is_control()
+                || matches!(ch, '<' | '>' | '"' | '`' | '{' | '}' | '|' | '\\' | '^')
+                || Some(ch as u32) == opening_quote.map(u32::from)
+            {
+                break;
+            }
+            end = separator + 3 + offset + ch.len_utf8();
+        }
+
+        let url = trim_url_end(&input[start..end]);
+        if url.len() > separator + 3 - start {
+            found.push(ExtractedUrl {
+                url: url.to_string(),
+                offset: start,
+            });
+        }
+        search_from = end.max(separator + 3);
+    }
+
+    found
+}
+
+fn is_scheme_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b'+' | b'-' | b'.')
+}
+
+fn trim_url_end(url: &str) -> &str {
+    let mut url = url;
+    loop {
+        let Some(last) = url.chars().last() else {
+            return url;
+        };
+        let unbalanced = |open: char, close: char| {
+            last == close && url.matches(close).count() > url.matches(open).count()
+        };
+        if matches!(last, '.' | ',' | ';' | ':' | '!' | '?' | '\'' | '*')
+            || unbalanced('(', ')')
+            || unbalanced('[', ']')
+        {
+            url = &url[..url.len() - last.len_utf8()];
+        } else {
+            return url;
+        }
+    }
+}
+
+/// Extracts link targets from HTML `href`, `src`, `srcset` and similar
+/// attributes, resolving relative URLs against `<base href>` or `base`.
+pub fn extract_from_html(input: &str, base: Option<&str>) -> Vec<ExtractedUrl> {
+    let mut base = base.map(str::to_string);
+    let mut found = Vec::new();
+    let mut pos = 0;
+
+    while let Some(relative) = input[pos..].find('<') {
+        let tag_start = pos + relative;
+        let rest = &input[tag_start..];
+
+        if rest.starts_with("<!--") {
+            pos = rest
+                .find("-->")
+                .map_or(input.len(), |end| tag_start + end + 3);
+            continue;
+        }
+
+        let Some(tag_len) = find_tag_end(rest) else {
+            break;
+        };
+        let tag = &input[tag_start..tag_start + tag_len];
+        pos = tag_start + tag_len;
+
+        let name_end = tag[1..]
+            .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+            .map_or(tag.len(), |i| i + 1);
+        let tag_name = tag[1..name_end].to_ascii_lowercase();
+        if tag_name.is_empty() || tag_name.starts_with('!') || tag_name.starts_with('?') {
+            continue;
+        }
+
+        for (name, value, value_offset) in parse_attributes(tag, name_end) {
+            let offset = tag_start + value_offset;
+            if tag_name == "base" && name == "href" {
+                base = match base.as_deref() {
+                    Some(current) => resolve_url(current, &value),
+                    None => Some(value.clone()),
+                };
+                continue;
+            }
+
+            if name == "srcset" {
+                for candidate in value.split(',') {
+                    if let Some(url) = candidate.split_whitespace().next() {
+                        push_resolved(&mut found, base.as_deref(), url, offset);
+                    }
+                }
+            } else if HTML_URL_ATTRIBUTES.contains(&name.as_str()) {
+                push_resolved(&mut found, base.as_deref(), &value, offset);
+            }
+        }
+
+        if matches!(tag_name.as_str(), "script" | "style") {
+            let closing = format!("</{tag_name}");
+            pos = find_ignore_ascii_case(&input[pos..], &closing)
+                .map_or(input.len(), |end| pos + end);
+        }
+    }
+
+    found
+}
+
+/// Finds `needle` in `haystack` ignoring ASCII case, without lowercasing a
+/// copy of the rest of the document.
+fn find_ignore_ascii_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .as_bytes()
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+fn find_tag_end(tag: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, ch) in tag.char_indices().skip(1) {
+        match (quote, ch) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(ch),
+            (None, '>') => return Some(i + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn parse_attributes(tag: &str, start: usize) -> Vec<(String, String, usize)> {
+    let bytes = tag.as_bytes();
+    let mut attributes = Vec::new();
+    let mut i = start;
+
+    while i < bytes.len() {
+        while i < bytes.len() && (bytes[i].is_ascii_whitespace() || bytes[i] == b'/') {
+            i += 1;
+        }
+        let name_start = i;
+        while i < bytes.len()
+            && !matches!(bytes[i], b'=' | b'>' | b'/')
+            && !bytes[i].is_ascii_whitespace()
+        {
+            i += 1;
+        }
+        if name_start == i {
+            break;
+        }
+        let name = tag[name_start..i].to_ascii_lowercase();
+
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        if i >= bytes.len() || bytes[i] != b'=' {
+            continue;
+        }
+        i += 1;
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+
+        let (value_start, value_end, next) = match bytes.get(i) {
+            Some(&quote @ (b'"' | b'\'')) => {
+                let end = tag[i + 1..]
+                    .find(quote as char)
+                    .map_or(bytes.len(), |e| i + 1 + e);
+                (i + 1, end, end + 1)
+            }
+            _ => {
+                let end = tag[i..]
+                    .find(|c: char| c.is_whitespace() || c == '>')
+                    .map_or(bytes.len(), |e| i + e);
+                (i, end, end)
+            }
+        };
+        attributes.push((
+            name,
+            decode_html_entities(&tag[value_start..value_end]),
+            value_start,
+        ));
+        i = next;
+    }
+
+    attributes
+}
+
+fn decode_html_entities(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(amp) = rest.find('&') {
+        output.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let Some(semi) = rest.find(';').filter(|&i| i <= 10) else {
+            output.push('&');
+            rest = &rest[1..];
+            continue;
+        };
+
+        let entity = &rest[1..semi];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|d| d.parse().ok()))
+                .and_then(char::from_u32),
+        };
+
+        match decoded {
+            Some(ch) => {
+                output.push(ch);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                output.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+fn push_resolved(
+    found: &mut Vec<ExtractedUrl>,
+    base: Option<&str>,
+    reference: &str,
+    offset: usize,
+) {
+    let reference = reference.trim();
+    if reference.is_empty() {
+        return;
+    }
+
+    let resolved = match base {
+        Some(base) => resolve_url(base, reference),
+        None => Some(reference.to_string()),
+    };
+    if let Some(url) = resolved.filter(|url| url.contains("://")) {
+        found.push(ExtractedUrl { url, offset });
+    }
+}
+
+/// Extracts inline links, images, autolinks and reference definitions from
+/// Markdown, plus any bare URLs in the surrounding text.
+pub fn extract_from_markdown(input: &str, base: Option<&str>) -> Vec<ExtractedUrl> {
+    let mut found = Vec::new();
+    let bytes = input.as_bytes();
+
+    for (i, _) in input.match_indices("](") {
+        let dest_start = i + 2;
+        let Some(dest_len) = markdown_destination_len(&input[dest_start..]) else {
+            continue;
+        };
+        let destination = &input[dest_start..dest_start + dest_len];
+        let (destination, offset) = match destination.strip_prefix('<') {
+            Some(inner) => (inner.trim_end_matches('>'), dest_start + 1),
+            None => (destination, dest_start),
+        };
+        push_resolved(&mut found, base, destination, offset);
+    }
+
+    let mut line_start = 0;
+    for line in input.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+        if let Some(label_end) = trimmed.strip_prefix('[').and_then(|r| r.find("]:")) {
+            let after = &trimmed[label_end + 3..];
+            let destination = after.split_whitespace().next().unwrap_or_default();
+            let offset =
+                line_start + indent + label_end + 3 + (after.len() - after.trim_start().len());
+            let destination = destination.trim_start_matches('<').trim_end_matches('>');
+            push_resolved(&mut found, base, destination, offset);
+        }
+        line_start += line.len();
+    }
+
+    for (i, _) in input.match_indices('<') {
+        let rest = &input[i + 1..];
+        let Some(end) = rest.find('>') else {
+            continue;
+        };
+        let candidate = &rest[..end];
+        if candidate.contains("://") && !candidate.contains(char::is_whitespace) {
+            push_resolved(&mut found, None, candidate, i + 1);
+        }
+    }
+
+    for bare in extract_from_text(input) {
+        let inside_link = found.iter().any(|link| link.offset == bare.offset);
+        let after_paren = bare.offset >= 2 && &bytes[bare.offset - 2..bare.offset] == b"](";
+        if !inside_link && !after_paren {
+            found.push(bare);
+        }
+    }
+
+    found.sort_by_key(|link| link.offset);
+    found.dedup_by_key(|link| link.offset);
+    found
+}
+
+/// Extracts absolute URLs and path-like string literals (`"/api/v2/users"`,
+/// `'./chunk.js'`, `` `//cdn.example.com/x` ``) from JavaScript source,
+/// skipping comments and regular expression literals. Escapes such as `\/` and
+/// `\u002F` are decoded, and template literals are cut at their first `${`.
+/// Relative paths are resolved against `base` and skipped without one.
+pub fn extract_from_js(input: &str, base: Option<&str>) -> Vec<ExtractedUrl> {
+    let bytes = input.as_bytes();
+    let mut found = Vec::new();
+    let mut pos = 0;
+    let mut previous = b';';
+
+    while pos < bytes.len() {
+        let b = bytes[pos];
+        match b {
+            b'/' if bytes.get(pos + 1) == Some(&b'/') => {
+                pos = input[pos..].find('\n').map_or(bytes.len(), |end| pos + end);
+                continue;
+            }
+            b'/' if bytes.get(pos + 1) == Some(&b'*') => {
+                pos = input[pos + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |end| pos + 2 + end + 2);
+                continue;
+            }
+            b'/' if b"(,=:[!&|?{};+-*%<>~^".contains(&previous) => {
+                pos = skip_regex_literal(bytes, pos + 1);
+                previous = b'/';
+                continue;
+            }
+            b'"' | b'\'' | b'`' => {
+                let (literal, offsets, end) = read_js_string(input, pos);
+                push_js_literal(&mut found, base, &literal, &offsets);
+                pos = end;
+                previous = b;
+                continue;
+            }
+            _ => {}
+        }
+        if !b.is_ascii_whitespace() {
+            previous = b;
+        }
+        pos += 1;
+    }
+
+    found
+}
+
+/// Reads the string literal opening at `start`, returning its decoded value,
+/// the source offset of each byte of the value, and the position just past
+/// the closing quote.
+fn read_js_string(input: &str, start: usize) -> (String, Vec<usize>, usize) {
+    let quote = input.as_bytes()[start] as char;
+    let mut value = String::new();
+    let mut offsets = Vec::new();
+    let mut chars = input[start + 1..].char_indices();
+    let mut in_template_expression = false;
+
+    while let Some((i, ch)) = chars.next() {
+        match ch {
+            '\\' => {
+                let Some((_, escaped)) = chars.next() else {
+                    break;
+                };
+                let decoded = match escaped {
+                    'x' => hex_escape(&mut chars, 2),
+                    'u' => {
+                        let rest = chars.as_str();
+                        if let Some(braced) = rest.strip_prefix('{') {
+                            let len = braced.find('}').unwrap_or(0);
+                            let code = u32::from_str_radix(&braced[..len], 16).ok();
+                            for _ in 0..len + 2 {
+                                chars.next();
+                            }
+                            code.and_then(char::from_u32)
+                        } else {
+                            hex_escape(&mut chars, 4)
+                        }
+                    }
+                    'n' | 'r' | 't' | 'b' | 'f' | 'v' | '0' => Some(' '),
+                    '\n' => None,
+                    other => Some(other),
+                };
+                if let Some(decoded) = decoded.filter(|_| !in_template_expression) {
+                    value.push(decoded);
+                    offsets.extend(std::iter::repeat_n(start + 1 + i, decoded.len_utf8()));
+                }
+            }
+            '\n' if quote != '`' => return (value, offsets, start + 1 + i),
+            '$' if quote == '`' && chars.as_str().starts_with('{') => {
+                in_template_expression = true;
+            }
+            c if c == quote => return (value, offsets, start + 1 + i + 1),
+            c => {
+                if !in_template_expression {
+                    value.push(c);
+                    offsets.extend(start + 1 + i..start + 1 + i + c.len_utf8());
+                }
+            }
+        }
+    }
+    (value, offsets, input.len())
+}
+
+fn hex_escape(chars: &mut std::str::CharIndices<'_>, len: usize) -> Option<char> {
+    let digits: String = chars.as_str().chars().take(len).collect();
+    let code = u32::from_str_radix(&digits, 16)
+        .ok()
+        .filter(|_| digits.len() == len)?;
+    for _ in 0..len {
+        chars.next();
+    }
+    char::from_u32(code)
+}
+
+fn skip_regex_literal(bytes: &[u8], mut pos: usize) -> usize {
+    let mut in_class = false;
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b'\\' => pos += 1,
+            b'[' => in_class = true,
+            b']' => in_class = false,
+            b'/' if !in_class => return pos + 1,
+            b'\n' => return pos,
+            _ => {}
+        }
+        pos += 1;
+    }
+    pos
+}
+
+/// `offsets` maps each byte of the decoded `literal` back to the source, so
+/// URLs found after an escape still point at where they start in the input.
+fn push_js_literal(
+    found: &mut Vec<ExtractedUrl>,
+    base: Option<&str>,
+    literal: &str,
+    offsets: &[usize],
+) {
+    let leading = literal.len() - literal.trim_start().len();
+    let literal = literal.trim();
+    let source = |decoded: usize| offsets.get(leading + decoded).copied().unwrap_or_default();
+    let offset = source(0);
+    if is_absolute_url(literal) {
+        found.push(ExtractedUrl {
+            url: trim_url_end(literal).to_string(),
+            offset,
+        });
+    } else if is_path_like(literal) {
+        if let Some(base) = base {
+            push_resolved(found, Some(base), literal, offset);
+        }
+    } else if literal.contains("://") {
+        found.extend(
+            extract_from_text(literal)
+                .into_iter()
+                .map(|url| ExtractedUrl {
+                    offset: source(url.offset),
+                    ..url
+                }),
+        );
+    }
+}
+
+fn is_absolute_url(value: &str) -> bool {
+    let Some((scheme, rest)) = value.split_once("://") else {
+        return false;
+    };
+    scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme.bytes().all(is_scheme_byte)
+        && !rest.is_empty()
+        && !value.contains(char::is_whitespace)
+}
+
+fn is_path_like(value: &str) -> bool {
+    if value.len() < 2
+        || value.contains(char::is_whitespace)
+        || value.contains(['<', '>', '"', '\'', '`', '{', '}', '|', '\\', '^', '(', ')'])
+    {
+        return false;
+    }
+    if let Some(rest) = value.strip_prefix("//") {
+        return rest.starts_with(|c: char| c.is_ascii_alphanumeric()) && rest.contains('.');
+    }
+    if value.starts_with("./") || value.starts_with("../") {
+        return true;
+    }
+    if let Some(rest) = value.strip_prefix('/') {
+        return rest.starts_with(|c: char| c.is_ascii_alphanumeric() || "_-.~%?#".contains(c));
+    }
+    let path = value.split(['?', '#']).next().unwrap_or_default();
+    path.contains('/') && JS_ENDPOINT_EXTENSIONS.iter().any(|ext| path.ends_with(ext))
+}
+
+fn markdown_destination_len(rest: &str) -> Option<usize> {
+    if rest.starts_with('<') {
+        return rest.find('>').map(|end| end + 1);
+    }
+
+    let mut depth = 0usize;
+    for (i, ch) in rest.char_indices() {
+        match ch {
+            '(' => depth += 1,
+            ')' if depth == 0 => return Some(i),
+            ')' => depth -= 1,
+            c if c.is_whitespace() => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn urls(found: Vec<ExtractedUrl>) -> Vec<String> {
+        found.into_iter().map(|link| link.url).collect()
+    }
+
+    #[test]
+    fn test_extract_from_text() {
+        let text = "See https://example.com/docs. Also (https://en.wikipedia.org/wiki/Rust_(programming_language)) \
+                    and \"http://quoted.example/a?b=1\", or 'ftp://files.example/x'!";
+        assert_eq!(
+            urls(extract_from_text(text)),
+            vec![
+                "https://example.com/docs",
+                "https://en.wikipedia.org/wiki/Rust_(programming_language)",
+                "http://quoted.example/a?b=1",
+                "ftp://files.example/x",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_from_text_offsets_and_noise() {
+        let found = extract_from_text("log: GET https://api.example.com/v1, done :// nothing");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].url, "https://api.example.com/v1");
+        assert_eq!(found[0].offset, 9);
+    }
+
+    #[test]
+    fn test_extract_from_html() {
+        let html = r#"<html><head><base href="https://example.com/app/"></head>
+<!-- <a href="https://commented.example/"> -->
+<a href="page.html?x=1&amp;y=2">Page</a>
+<img src='/logo.png' srcset="img-1x.png 1x, https://cdn.example.com/img-2x.png 2x">
+<a href="mailto:someone@example.com">Mail</a>
+<script>var u = "<a href='https://inside-script.example/'>";</SCRIPT>
+<form action=//forms.example.com/submit></form>"#;
+
+        assert_eq!(
+            urls(extract_from_html(html, None)),
+            vec![
+                "https://example.com/app/page.html?x=1&y=2",
+                "https://example.com/logo.png",
+                "https://example.com/app/img-1x.png",
+                "https://cdn.example.com/img-2x.png",
+                "https://forms.example.com/submit",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_from_html_without_base_skips_relative() {
+        let html = r#"<a href="/relative">x</a><a href="https://example.com/abs">y</a>"#;
+        assert_eq!(
+            urls(extract_from_html(html, None)),
+            vec!["https://example.com/abs"]
+        );
+        assert_eq!(
+            urls(extract_from_html(html, Some("https://site.example/"))),
+            vec!["https://site.example/relative", "https://example.com/abs"]
+        );
+    }
+
+    #[test]
+    fn test_extract_from_markdown() {
+        let markdown = "# Links\n\
+                        See [the docs](https://example.com/docs \"Docs\") and ![logo](/img/logo.png).\n\
+                        Autolink: <https://auto.example.com/path>\n\
+                        Bare: https://bare.example.com/x.\n\
+                        \n\
+                        [ref]: https://ref.example.com/page\n";
+
+        assert_eq!(
+            urls(extract_from_markdown(
+                markdown,
+                Some("https://example.com/")
+            )),
+            vec![
+                "https://example.com/docs",
+                "https://example.com/img/logo.png",
+                "https://auto.example.com/path",
+                "https://bare.example.com/x",
+                "https://ref.example.com/page",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_html_entities() {
+        assert_eq!(
+            decode_html_entities("a&amp;b&#x2F;c&#47;d&bogus"),
+            "a&b/c/d&bogus"
+        );
+    }
+
+    #[test]
+    fn test_extract_from_js() {
+        let js = r#"/* see "https://license.example/" */ const api="/api/v2/users",cdn='//cdn.example.com/app.js';
+// fetch("/commented/out")
+fetch(`${host}/ignored`);fetch(`/api/v2/items/${id}?full=1`);
+var re=/"\/not\/a\/string"/g, esc="https:\/\/escaped.example.com\/x", rel="./chunk.8f3a.js";
+var mime="text/html", tpl="users/list.json", msg="Go\u0020to https://help.example.com/faq now";"#;
+
+        let found = extract_from_js(js, Some("https://app.example.com/static/main.js"));
+        assert_eq!(
+            urls(found.clone()),
+            vec![
+                "https://app.example.com/api/v2/users",
+                "https://cdn.example.com/app.js",
+                "https://app.example.com/api/v2/items/",
+                "https://escaped.example.com/x",
+                "https://app.example.com/static/chunk.8f3a.js",
+                "https://app.example.com/static/users/list.json",
+                "https://help.example.com/faq",
+            ]
+        );
+        assert_eq!(found[0].offset, js.find("/api/v2/users").unwrap());
+        assert_eq!(found[3].offset, js.find("https:\\/\\/escaped").unwrap());
+        assert_eq!(found[6].offset, js.find("https://help").unwrap());
+    }
+
+    #[test]
+    fn test_extract_from_js_without_base_keeps_absolute_urls() {
+        let js = r#"fetch("/api/me");fetch("https://api.example.com/v1/login",{method:"POST"})"#;
+        assert_eq!(
+            urls(extract_from_js(js, None)),
+            vec!["https://api.example.com/v1/login"]
+        );
+    }
+}
@@ -0,0 +1,591 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use regex::Regex;
+
+use crate::formatter::{is_number, UrlRecord};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterError {
+    /// Character index in the expression where the error was found.
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for FilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "at column {}: {}", self.position + 1, self.message)
+    }
+}
+
+impl std::error::Error for FilterError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Number(String),
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Match,
+    NotMatch,
+    And,
+    Or,
+    Not,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+    In,
+    Like,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Ident(name) => write!(f, "field '{name}'"),
+            Token::Str(value) => write!(f, "string \"{value}\""),
+            Token::Number(value) => write!(f, "number {value}"),
+            Token::Eq => write!(f, "'=='"),
+            Token::Ne => write!(f, "'!='"),
+            Token::Lt => write!(f, "'<'"),
+            Token::Le => write!(f, "'<='"),
+            Token::Gt => write!(f, "'>'"),
+            Token::Ge => write!(f, "'>='"),
+            Token::Match => write!(f, "'~'"),
+            Token::NotMatch => write!(f, "'!~'"),
+            Token::And => write!(f, "'&&'"),
+            Token::Or => write!(f, "'||'"),
+            Token::Not => write!(f, "'!'"),
+            Token::LParen => write!(f, "'('"),
+            Token::RParen => write!(f, "')'"),
+            Token::LBracket => write!(f, "'['"),
+            Token::RBracket => write!(f, "']'"),
+            Token::Comma => write!(f, "','"),
+            Token::In => write!(f, "'in'"),
+            Token::Like => write!(f, "'like'"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Present(String),
+    Compare(String, CompareOp, String),
+    Regex(String, Regex),
+    In(String, Vec<String>),
+}
+
+/// A boolean expression over `UrlRecord` fields, as passed to `--filter`.
+///
+/// Supports `==`, `!=`, `<`, `<=`, `>`, `>=` (numeric when both sides are
+/// numbers), `~`/`!~` regex matches, `like` globs, `in [...]` lists, bare
+/// field names as presence checks, and `!`, `&&`, `||` with parentheses.
+/// Comparisons against a missing field are false, except `!=` and `!~`.
+#[derive(Debug, Clone)]
+pub struct Filter {
+    expr: Expr,
+    fields: Vec<String>,
+}
+
+impl Filter {
+    pub fn parse(input: &str) -> Result<Self, FilterError> {
+        Self::parse_tokens(input).map_err(|e| FilterError {
+            position: input
+                .char_indices()
+                .take_while(|&(i, _)| i < e.position)
+                .count(),
+            ..e
+        })
+    }
+
+    /// Parses `input`, reporting errors at byte offsets.
+    fn parse_tokens(input: &str) -> Result<Self, FilterError> {
+        let tokens = tokenize(input)?;
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            end: input.len(),
+            fields: Vec::new(),
+        };
+        let expr = parser.parse_or()?;
+        if let Some((token, position)) = parser.tokens.get(parser.pos) {
+            return Err(FilterError {
+                position: *position,
+                message: format!("unexpected {token}"),
+            });
+        }
+
+        Ok(Filter {
+            expr,
+            fields: parser.fields,
+        })
+    }
+
+    /// Field names referenced by the expression, in order of appearance.
+    pub fn fields(&self) -> &[String] {
+        &self.fields
+    }
+
+    pub fn matches(&self, record: &UrlRecord) -> bool {
+        evaluate(&self.expr, record)
+    }
+}
+
+fn evaluate(expr: &Expr, record: &UrlRecord) -> bool {
+    match expr {
+        Expr::And(left, right) => evaluate(left, record) && evaluate(right, record),
+        Expr::Or(left, right) => evaluate(left, record) || evaluate(right, record),
+        Expr::Not(inner) => !evaluate(inner, record),
+        Expr::Present(field) => record.get_field(field).is_some_and(|v| !v.is_empty()),
+        Expr::Compare(field, op, expected) => match record.get_field(field) {
+            Some(value) => {
+                let ordering = compare_values(value, expected);
+                match op {
+                    CompareOp::Eq => ordering == Ordering::Equal,
+                    CompareOp::Ne => ordering != Ordering::Equal,
+                    CompareOp::Lt => ordering == Ordering::Less,
+                    CompareOp::Le => ordering != Ordering::Greater,
+                    CompareOp::Gt => ordering == Ordering::Greater,
+                    CompareOp::Ge => ordering != Ordering::Less,
+                }
+            }
+            None => *op == CompareOp::Ne,
+        },
+        Expr::Regex(field, regex) => record
+            .get_field(field)
+            .is_some_and(|value| regex.is_match(value)),
+        Expr::In(field, values) => record
+            .get_field(field)
+            .is_some_and(|value| values.iter().any(|v| compare_values(value, v).is_eq())),
+    }
+}
+
+/// Values that are both plain decimal numbers, such as ports and scores,
+/// compare numerically; anything else, including `nan` and `inf`, compares
+/// as text.
+fn compare_values(left: &str, right: &str) -> Ordering {
+    if !(is_number(left) && is_number(right)) {
+        return left.cmp(right);
+    }
+    match (left.parse::<f64>(), right.parse::<f64>()) {
+        (Ok(l), Ok(r)) => l.partial_cmp(&r).unwrap_or(Ordering::Equal),
+        _ => left.cmp(right),
+    }
+}
+
+fn glob_to_regex(glob: &str) -> String {
+    let mut pattern = String::from("^");
+    for ch in glob.chars() {
+        match ch {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            _ => pattern.push_str(&regex::escape(&ch.to_string())),
+        }
+    }
+    pattern.push('$');
+    pattern
+}
+
+fn tokenize(input: &str) -> Result<Vec<(Token, usize)>, FilterError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(start, ch)) = chars.peek() {
+        if ch.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let mut two_char = |second: char, double: Token, single: Option<Token>| {
+            chars.next();
+            if chars.peek().map(|&(_, c)| c) == Some(second) {
+                chars.next();
+                Ok(double)
+            } else {
+                single.ok_or_else(|| FilterError {
+                    position: start,
+                    message: format!("expected '{ch}{second}'"),
+                })
+            }
+        };
+
+        let token = match ch {
+            '=' => two_char('=', Token::Eq, None)?,
+            '&' => two_char('&', Token::And, None)?,
+            '|' => two_char('|', Token::Or, None)?,
+            '<' => two_char('=', Token::Le, Some(Token::Lt))?,
+            '>' => two_char('=', Token::Ge, Some(Token::Gt))?,
+            '!' => {
+                chars.next();
+                match chars.peek().map(|&(_, c)| c) {
+                    Some('=') => {
+                        chars.next();
+                        Token::Ne
+                    }
+                    Some('~') => {
+                        chars.next();
+                        Token::NotMatch
+                    }
+                    _ => Token::Not,
+                }
+            }
+            '~' | '(' | ')' | '[' | ']' | ',' => {
+                chars.next();
+                match ch {
+                    '~' => Token::Match,
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    '[' => Token::LBracket,
+                    ']' => Token::RBracket,
+                    _ => Token::Comma,
+                }
+            }
+            '"' | '\'' => {
+                chars.next();
+                let mut value = String::new();
+                let mut closed = false;
+                while let Some((_, c)) = chars.next() {
+                    if c == ch {
+                        closed = true;
+                        break;
+                    }
+                    if c == '\\' {
+                        match chars.next() {
+                            Some((_, escaped)) => value.push(escaped),
+                            None => break,
+                        }
+                    } else {
+                        value.push(c);
+                    }
+                }
+                if !closed {
+                    return Err(FilterError {
+                        position: start,
+                        message: "unterminated string".to_string(),
+                    });
+                }
+                Token::Str(value)
+            }
+            c if c.is_ascii_digit() || c == '-' => {
+                let mut value = String::new();
+                while let Some(&(_, c)) = chars.peek() {
+                    if c.is_ascii_digit() || c == '.' || (c == '-' && value.is_empty()) {
+                        value.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                if value.parse::<f64>().is_err() {
+                    return Err(FilterError {
+                        position: start,
+                        message: format!("invalid number '{value}'"),
+                    });
+                }
+                Token::Number(value)
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut name = String::new();
+                while let Some(&(_, c)) = chars.peek() {
+                    if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' {
+                        name.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                match name.as_str() {
+                    "in" => Token::In,
+                    "like" => Token::Like,
+                    _ => Token::Ident(name),
+                }
+            }
+            _ => {
+                return Err(FilterError {
+                    position: start,
+                    message: format!("unexpected character '{ch}'"),
+                })
+            }
+        };
+        tokens.push((token, start));
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    end: usize,
+    fields: Vec<String>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .map_or(self.end, |(_, position)| *position)
+    }
+
+    fn error(&self, message: impl Into<String>) -> FilterError {
+        FilterError {
+            position: self.position(),
+            message: message.into(),
+        }
+    }
+
+    fn found(&self) -> String {
+        self.peek()
+            .map_or("end of expression".to_string(), |token| token.to_string())
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).map(|(token, _)| token.clone());
+        self.pos += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, FilterError> {
+        let mut left = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            let right = self.parse_and()?;
+            left = Expr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, FilterError> {
+        let mut left = self.parse_unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.next();
+            let right = self.parse_unary()?;
+            left = Expr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, FilterError> {
+        match self.peek() {
+            Some(Token::Not) => {
+                self.next();
+                Ok(Expr::Not(Box::new(self.parse_unary()?)))
+            }
+            Some(Token::LParen) => {
+                self.next();
+                let expr = self.parse_or()?;
+                if self.peek() != Some(&Token::RParen) {
+                    return Err(self.error(format!("expected ')', found {}", self.found())));
+                }
+                self.next();
+                Ok(expr)
+            }
+            Some(Token::Ident(_)) => self.parse_predicate(),
+            _ => Err(self.error(format!(
+                "expected a field name, '!' or '(', found {}",
+                self.found()
+            ))),
+        }
+    }
+
+    fn parse_predicate(&mut self) -> Result<Expr, FilterError> {
+        let Some(Token::Ident(field)) = self.next() else {
+            unreachable!("parse_predicate is only called on identifiers");
+        };
+        if !self.fields.contains(&field) {
+            self.fields.push(field.clone());
+        }
+
+        let op = match self.peek() {
+            Some(Token::Eq) => CompareOp::Eq,
+            Some(Token::Ne) => CompareOp::Ne,
+            Some(Token::Lt) => CompareOp::Lt,
+            Some(Token::Le) => CompareOp::Le,
+            Some(Token::Gt) => CompareOp::Gt,
+            Some(Token::Ge) => CompareOp::Ge,
+            Some(Token::Match) | Some(Token::NotMatch) => {
+                let negated = self.next() == Some(Token::NotMatch);
+                let pattern = self.parse_string("a regex string")?;
+                let regex = self.compile(&pattern)?;
+                let expr = Expr::Regex(field, regex);
+                return Ok(if negated {
+                    Expr::Not(Box::new(expr))
+                } else {
+                    expr
+                });
+            }
+            Some(Token::Like) => {
+                self.next();
+                let glob = self.parse_string("a glob string")?;
+                let regex = self.compile(&glob_to_regex(&glob))?;
+                return Ok(Expr::Regex(field, regex));
+            }
+            Some(Token::In) => {
+                self.next();
+                return Ok(Expr::In(field, self.parse_list()?));
+            }
+            _ => return Ok(Expr::Present(field)),
+        };
+
+        self.next();
+        let value = self.parse_value()?;
+        Ok(Expr::Compare(field, op, value))
+    }
+
+    fn compile(&self, pattern: &str) -> Result<Regex, FilterError> {
+        let position = self.tokens[self.pos - 1].1;
+        Regex::new(pattern).map_err(|e| FilterError {
+            position,
+            message: format!("invalid regex: {e}"),
+        })
+    }
+
+    fn parse_string(&mut self, expected: &str) -> Result<String, FilterError> {
+        match self.peek() {
+            Some(Token::Str(_)) => match self.next() {
+                Some(Token::Str(value)) => Ok(value),
+                _ => unreachable!(),
+            },
+            _ => Err(self.error(format!("expected {expected}, found {}", self.found()))),
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<String, FilterError> {
+        match self.peek() {
+            Some(Token::Str(_)) | Some(Token::Number(_)) => match self.next() {
+                Some(Token::Str(value)) | Some(Token::Number(value)) => Ok(value),
+                _ => unreachable!(),
+            },
+            _ => Err(self.error(format!(
+                "expected a string or number, found {}",
+                self.found()
+            ))),
+        }
+    }
+
+    fn parse_list(&mut self) -> Result<Vec<String>, FilterError> {
+        if self.peek() != Some(&Token::LBracket) {
+            return Err(self.error(format!("expected '[', found {}", self.found())));
+        }
+        self.next();
+
+        let mut values = Vec::new();
+        if self.peek() == Some(&Token::RBracket) {
+            self.next();
+            return Ok(values);
+        }
+        loop {
+            values.push(self.parse_value()?);
+            match self.next() {
+                Some(Token::Comma) => continue,
+                Some(Token::RBracket) => return Ok(values),
+                _ => {
+                    self.pos -= 1;
+                    return Err(self.error(format!("expected ',' or ']', found {}", self.found())));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formatter::to_record;
+
+    fn matches(filter: &str, url: &str) -> bool {
+        Filter::parse(filter)
+            .unwrap()
+            .matches(&to_record(url).unwrap())
+    }
+
+    #[test]
+    fn test_comparisons() {
+        let url = "https://api.example.com:8080/api/users?id=1";
+        assert!(matches(r#"domain == "example.com""#, url));
+        assert!(matches(r#"port != "443""#, url));
+        assert!(matches("port > 1024 && port <= 8080", url));
+        assert!(!matches("port < 100", url));
+        assert!(matches(r#"port != "443""#, "https://example.com/"));
+        assert!(!matches(r#"port == "443""#, "https://example.com/"));
+        assert!(matches("port > 999", "https://example.com:8080/"));
+        assert!(matches(r#"subdomain < "nan""#, "https://inf.example.com/"));
+        assert!(!matches(r#"subdomain > "nan""#, "https://inf.example.com/"));
+    }
+
+    #[test]
+    fn test_regex_glob_and_lists() {
+        let url = "https://api.example.com/api/v2/users";
+        assert!(matches(r#"path ~ "^/api/""#, url));
+        assert!(!matches(r#"path !~ "^/api/""#, url));
+        assert!(matches(r#"hostname like "*.example.com""#, url));
+        assert!(!matches(r#"hostname like "*.example.org""#, url));
+        assert!(matches(r#"subdomain in ["api", "www"]"#, url));
+        assert!(!matches("scheme in ['http']", url));
+    }
+
+    #[test]
+    fn test_presence_and_boolean_logic() {
+        let url = "https://example.com/search?q=rust";
+        assert!(matches("query", url));
+        assert!(matches("!fragment", url));
+        assert!(matches(r#"fragment || (query && scheme == "https")"#, url));
+        assert!(!matches(r#"!(query && scheme == "https")"#, url));
+    }
+
+    #[test]
+    fn test_referenced_fields() {
+        let filter = Filter::parse(r#"domain == "a.com" || risk_score > 50 && domain"#).unwrap();
+        assert_eq!(filter.fields(), ["domain", "risk_score"]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Filter::parse(r#"domain = "x""#).unwrap_err();
+        assert_eq!(err.to_string(), "at column 8: expected '=='");
+
+        let err = Filter::parse(r#"domain == "x" &&"#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "at column 17: expected a field name, '!' or '(', found end of expression"
+        );
+
+        let err = Filter::parse(r#"(domain == "x""#).unwrap_err();
+        assert!(err.message.starts_with("expected ')'"));
+
+        let err = Filter::parse(r#"path ~ "(""#).unwrap_err();
+        assert!(err.message.starts_with("invalid regex"));
+
+        let err = Filter::parse(r#"domain == "x"#).unwrap_err();
+        assert_eq!(err.message, "unterminated string");
+
+        let err = Filter::parse(r#"port in [80 443]"#).unwrap_err();
+        assert_eq!(err.message, "expected ',' or ']', found number 443");
+
+        let err = Filter::parse(r#"path == "/é" = 1"#).unwrap_err();
+        assert_eq!(err.position, 13);
+    }
+}
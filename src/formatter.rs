@@ -1,11 +1,17 @@
 use clap::ValueEnum;
-use serde::Serialize;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
 use std::str::FromStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
-use crate::{extract_url_components, parse_url};
+use crate::color::{Component, Palette};
+use crate::{extract_url_components, parse_url, UrlParts};
 
-#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
 pub enum EscapeMode {
+    #[default]
     None,
     Shell,
     Csv,
@@ -13,28 +19,92 @@ pub enum EscapeMode {
     Sql,
 }
 
-impl Default for EscapeMode {
-    fn default() -> Self {
-        EscapeMode::None
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
 pub enum SqlDialect {
+    #[default]
     Postgres,
     Mysql,
     Sqlite,
     Generic,
 }
 
-impl Default for SqlDialect {
-    fn default() -> Self {
-        SqlDialect::Postgres
+impl SqlDialect {
+    /// Quotes a column name: backticks for MySQL, ANSI double quotes for
+    /// everything else.
+    fn quote_identifier(&self, name: &str) -> String {
+        let quote = match self {
+            SqlDialect::Mysql => "`",
+            _ => "\"",
+        };
+        format!("{quote}{}{quote}", name.replace(quote, &quote.repeat(2)))
+    }
+
+    /// Quotes a string literal. MySQL treats backslashes in strings as
+    /// escapes by default, so they are doubled there as well.
+    fn quote_string(&self, value: &str) -> String {
+        match self {
+            SqlDialect::Mysql => format!(
+                "'{}'",
+                value
+                    .replace('\\', "\\\\")
+                    .replace('\'', "''")
+                    .replace('\0', "\\0")
+            ),
+            _ => sql_escape(value),
+        }
+    }
+
+    /// Validates a `--sql-table` name, either `table` or `schema.table`,
+    /// and quotes each part. Parts must be letters, digits and underscores
+    /// and must not start with a digit, so the name cannot carry SQL.
+    pub fn quote_table_name(&self, name: &str) -> Result<String, String> {
+        let parts: Vec<&str> = name.split('.').collect();
+        if parts.len() > 2 {
+            return Err(format!(
+                "{name:?} has more than one '.'; use table or schema.table"
+            ));
+        }
+        for part in &parts {
+            let valid = part
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid {
+                return Err(format!(
+                    "{name:?} is not a valid table name; use letters, digits and underscores"
+                ));
+            }
+        }
+        let quoted: Vec<String> = parts
+            .iter()
+            .map(|part| self.quote_identifier(part))
+            .collect();
+        Ok(quoted.join("."))
+    }
+
+    /// The surrogate key column `CREATE TABLE` adds.
+    fn id_column(&self) -> &'static str {
+        match self {
+            SqlDialect::Postgres | SqlDialect::Generic => {
+                "BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY"
+            }
+            SqlDialect::Mysql => "BIGINT AUTO_INCREMENT PRIMARY KEY",
+            SqlDialect::Sqlite => "INTEGER PRIMARY KEY AUTOINCREMENT",
+        }
+    }
+
+    fn begin_transaction(&self) -> &'static str {
+        match self {
+            SqlDialect::Mysql => "START TRANSACTION;",
+            _ => "BEGIN;",
+        }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
 pub enum Format {
+    #[default]
     Plain,
     Tsv,
     Csv,
@@ -42,12 +112,11 @@ pub enum Format {
     Jsonl,
     Custom,
     Sql,
-}
-
-impl Default for Format {
-    fn default() -> Self {
-        Format::Plain
-    }
+    Table,
+    Markdown,
+    Html,
+    Xml,
+    Yaml,
 }
 
 impl FromStr for Format {
@@ -62,54 +131,206 @@ impl FromStr for Format {
             "jsonl" => Ok(Format::Jsonl),
             "custom" => Ok(Format::Custom),
             "sql" => Ok(Format::Sql),
+            "table" => Ok(Format::Table),
+            "markdown" => Ok(Format::Markdown),
+            "html" => Ok(Format::Html),
+            "xml" => Ok(Format::Xml),
+            "yaml" => Ok(Format::Yaml),
             _ => Err(format!(
-                "Invalid format: {s}. Valid formats: plain, tsv, csv, json, jsonl, custom, sql"
+                "Invalid format: {s}. Valid formats: plain, tsv, csv, json, jsonl, custom, sql, table, markdown, html, xml, yaml"
             )),
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// Fields [`UrlRecord::get_field`] reads from the URL's components rather
+/// than from the record's extra fields.
+pub const RECORD_FIELDS: &[&str] = &[
+    "url",
+    "scheme",
+    "username",
+    "host",
+    "hostname",
+    "subdomain",
+    "domain",
+    "port",
+    "path",
+    "query",
+    "fragment",
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct UrlRecord {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_text"
+    )]
     pub url: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_text"
+    )]
     pub scheme: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_text"
+    )]
     pub username: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_text"
+    )]
     pub host: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_text"
+    )]
     pub hostname: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_text"
+    )]
     pub subdomain: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_text"
+    )]
     pub domain: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_text"
+    )]
     pub port: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_path"
+    )]
     pub path: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_query"
+    )]
     pub query: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_text"
+    )]
     pub fragment: Option<String>,
+    #[serde(flatten, deserialize_with = "deserialize_extra")]
+    pub extra: BTreeMap<String, String>,
+}
+
+/// Reads a component that `--typed` output may have written as a number,
+/// such as `port`.
+fn deserialize_text<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::Null => None,
+        serde_json::Value::String(s) => Some(s),
+        other => Some(other.to_string()),
+    })
+}
+
+/// Reads a path written as a string or as the segment array of `--typed`
+/// output.
+fn deserialize_path<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::Array(segments) => {
+            let segments = segments
+                .iter()
+                .map(|segment| segment.as_str().ok_or("path segments must be strings"))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(serde::de::Error::custom)?;
+            Ok(Some(format!("/{}", segments.join("/"))))
+        }
+        serde_json::Value::Null => Ok(None),
+        serde_json::Value::String(s) => Ok(Some(s)),
+        other => Err(serde::de::Error::custom(format!(
+            "expected a path string or array, found {other}"
+        ))),
+    }
+}
+
+/// Reads a query written as a string, as the array of pairs of `--typed`
+/// output, or as an object of parameters.
+fn deserialize_query<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    let pairs: Vec<(String, Option<String>)> = match value {
+        serde_json::Value::Null => return Ok(None),
+        serde_json::Value::String(s) => return Ok(Some(s)),
+        serde_json::Value::Object(object) => object
+            .into_iter()
+            .map(|(key, value)| Ok((key, query_value(value)?)))
+            .collect::<Result<_, String>>()
+            .map_err(serde::de::Error::custom)?,
+        serde_json::Value::Array(items) => items
+            .into_iter()
+            .map(|item| match item {
+                serde_json::Value::Array(mut pair) if pair.len() == 2 => {
+                    let value = query_value(pair.pop().unwrap_or_default())?;
+                    match pair.pop() {
+                        Some(serde_json::Value::String(key)) => Ok((key, value)),
+                        _ => Err("query keys must be strings".to_string()),
+                    }
+                }
+                _ => Err("query pairs must be [key, value] arrays".to_string()),
+            })
+            .collect::<Result<_, String>>()
+            .map_err(serde::de::Error::custom)?,
+        other => {
+            return Err(serde::de::Error::custom(format!(
+                "expected a query string, object or array, found {other}"
+            )))
+        }
+    };
+    let params: Vec<String> = pairs
+        .into_iter()
+        .map(|(key, value)| match value {
+            Some(value) => format!("{key}={value}"),
+            None => key,
+        })
+        .collect();
+    Ok(Some(format!("?{}", params.join("&"))))
+}
+
+fn query_value(value: serde_json::Value) -> Result<Option<String>, String> {
+    match value {
+        serde_json::Value::Null => Ok(None),
+        serde_json::Value::String(s) => Ok(Some(s)),
+        other => Ok(Some(other.to_string())),
+    }
+}
+
+/// Reads extra fields written by any output format: numbers and booleans are
+/// kept as their JSON text, nulls are dropped and nested values are stored as
+/// compact JSON.
+fn deserialize_extra<'de, D>(deserializer: D) -> Result<BTreeMap<String, String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let values = BTreeMap::<String, serde_json::Value>::deserialize(deserializer)?;
+    Ok(values
+        .into_iter()
+        .filter_map(|(name, value)| match value {
+            serde_json::Value::Null => None,
+            serde_json::Value::String(s) => Some((name, s)),
+            other => Some((name, other.to_string())),
+        })
+        .collect())
 }
 
 impl UrlRecord {
     pub fn new() -> Self {
-        Self {
-            url: None,
-            scheme: None,
-            username: None,
-            host: None,
-            hostname: None,
-            subdomain: None,
-            domain: None,
-            port: None,
-            path: None,
-            query: None,
-            fragment: None,
-        }
+        Self::default()
     }
 
     pub fn get_field(&self, field: &str) -> Option<&str> {
@@ -125,12 +346,47 @@ impl UrlRecord {
             "path" => self.path.as_deref(),
             "query" => self.query.as_deref(),
             "fragment" => self.fragment.as_deref(),
-            _ => None,
+            _ => self.extra.get(field).map(String::as_str),
+        }
+    }
+
+    pub fn set_extra(&mut self, field: &str, value: impl Into<String>) {
+        self.extra.insert(field.to_string(), value.into());
+    }
+
+    /// Builds a URL from the record's components with [`assemble_url`].
+    pub fn compose_url(&self) -> Result<String, String> {
+        assemble_url(|name| self.get_field(name))
+    }
+
+    /// Fills components this record lacks from `other`, keeping the values
+    /// already present.
+    pub fn fill_missing(&mut self, other: UrlRecord) {
+        let pairs = [
+            (&mut self.url, other.url),
+            (&mut self.scheme, other.scheme),
+            (&mut self.username, other.username),
+            (&mut self.host, other.host),
+            (&mut self.hostname, other.hostname),
+            (&mut self.subdomain, other.subdomain),
+            (&mut self.domain, other.domain),
+            (&mut self.port, other.port),
+            (&mut self.path, other.path),
+            (&mut self.query, other.query),
+            (&mut self.fragment, other.fragment),
+        ];
+        for (mine, theirs) in pairs {
+            if mine.is_none() {
+                *mine = theirs;
+            }
+        }
+        for (name, value) in other.extra {
+            self.extra.entry(name).or_insert(value);
         }
     }
 }
 
-fn select_fields(record: &UrlRecord, fields: &[&str], null_value: &str) -> Vec<String> {
+pub(crate) fn select_fields(record: &UrlRecord, fields: &[&str], null_value: &str) -> Vec<String> {
     fields
         .iter()
         .map(|field| {
@@ -142,9 +398,26 @@ fn select_fields(record: &UrlRecord, fields: &[&str], null_value: &str) -> Vec<S
         .collect()
 }
 
-pub fn print_plain(records: &[UrlRecord], fields: &[&str], null_value: &str, no_newline: bool) {
+/// Prints fields separated by spaces, highlighting URL components with
+/// `palette` when color is enabled.
+pub fn print_plain(
+    records: &[UrlRecord],
+    fields: &[&str],
+    null_value: &str,
+    palette: Option<&Palette>,
+    no_newline: bool,
+) {
     for (i, record) in records.iter().enumerate() {
-        let row = select_fields(record, fields, null_value);
+        let row = match palette {
+            Some(palette) => fields
+                .iter()
+                .map(|field| match record.get_field(field) {
+                    Some(value) => palette.highlight_field(field, value),
+                    None => palette.paint(Component::Null, null_value),
+                })
+                .collect(),
+            None => select_fields(record, fields, null_value),
+        };
         let line = row.join(" ");
         if no_newline && i == records.len() - 1 {
             print!("{line}");
@@ -154,22 +427,259 @@ pub fn print_plain(records: &[UrlRecord], fields: &[&str], null_value: &str, no_
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum TableStyle {
+    #[default]
+    Unicode,
+    Ascii,
+}
+
+impl TableStyle {
+    /// Border characters: horizontal, vertical, then the corners and
+    /// junctions of the top, middle and bottom rules from left to right.
+    fn borders(&self) -> (char, char, [[char; 3]; 3]) {
+        match self {
+            TableStyle::Unicode => (
+                '─',
+                '│',
+                [['┌', '┬', '┐'], ['├', '┼', '┤'], ['└', '┴', '┘']],
+            ),
+            TableStyle::Ascii => ('-', '|', [['+'; 3]; 3]),
+        }
+    }
+
+    fn ellipsis(&self) -> &'static str {
+        match self {
+            TableStyle::Unicode => "…",
+            TableStyle::Ascii => "...",
+        }
+    }
+}
+
+/// Renders records as a bordered table with a header row. Values wider
+/// than `max_width` terminal columns are truncated with an ellipsis (0 means
+/// no limit), and columns whose values are all numbers, such as `port`, are
+/// right-aligned. Wide characters such as CJK count as two columns.
+pub fn render_table(
+    records: &[UrlRecord],
+    fields: &[&str],
+    style: TableStyle,
+    max_width: usize,
+    null_value: &str,
+) -> String {
+    let truncate = |value: &str| {
+        let ellipsis = style.ellipsis();
+        if max_width == 0 || value.width() <= max_width {
+            return value.to_string();
+        }
+        let budget = max_width
+            .checked_sub(ellipsis.width())
+            .filter(|&budget| budget > 0)
+            .unwrap_or(max_width);
+        let mut kept = String::new();
+        let mut width = 0;
+        for c in value.chars() {
+            width += c.width().unwrap_or_default();
+            if width > budget {
+                break;
+            }
+            kept.push(c);
+        }
+        if budget < max_width {
+            kept.push_str(ellipsis);
+        }
+        kept
+    };
+    let header: Vec<String> = fields
+        .iter()
+        .map(|field| truncate(&table_escape(field)))
+        .collect();
+    let rows: Vec<Vec<String>> = records
+        .iter()
+        .map(|record| {
+            fields
+                .iter()
+                .map(|field| match record.get_field(field) {
+                    Some(value) => truncate(&table_escape(value)),
+                    None => truncate(null_value),
+                })
+                .collect()
+        })
+        .collect();
+
+    let numeric = numeric_columns(records, fields);
+    let widths: Vec<usize> = (0..fields.len())
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].width())
+                .chain([header[column].width()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let (horizontal, vertical, rules) = style.borders();
+    let rule = |[left, middle, right]: [char; 3]| {
+        let segments: Vec<String> = widths
+            .iter()
+            .map(|width| horizontal.to_string().repeat(width + 2))
+            .collect();
+        format!("{left}{}{right}\n", segments.join(&middle.to_string()))
+    };
+    let line = |cells: &[String]| {
+        let cells: Vec<String> = cells
+            .iter()
+            .enumerate()
+            .map(|(column, cell)| {
+                let padding = " ".repeat(widths[column] - cell.width());
+                if numeric[column] {
+                    format!(" {padding}{cell} ")
+                } else {
+                    format!(" {cell}{padding} ")
+                }
+            })
+            .collect();
+        format!(
+            "{vertical}{}{vertical}\n",
+            cells.join(&vertical.to_string())
+        )
+    };
+
+    let mut table = rule(rules[0]);
+    table.push_str(&line(&header));
+    table.push_str(&rule(rules[1]));
+    for row in &rows {
+        table.push_str(&line(row));
+    }
+    table.push_str(&rule(rules[2]));
+    table
+}
+
+/// Escapes a table cell the way [`tsv_escape`] does, writing other control
+/// characters as `\u{..}` so a value cannot break rows or send escape
+/// sequences to the terminal.
+fn table_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c == '\\' || c.is_control() {
+            escaped.extend(c.escape_default());
+        } else {
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
+pub fn print_table(
+    records: &[UrlRecord],
+    fields: &[&str],
+    style: TableStyle,
+    max_width: usize,
+    null_value: &str,
+    no_newline: bool,
+) {
+    print_rendered(
+        &render_table(records, fields, style, max_width, null_value),
+        no_newline,
+    );
+}
+
+/// Prints output rendered as a whole, dropping its final newline when
+/// `no_newline` is set.
+pub(crate) fn print_rendered(text: &str, no_newline: bool) {
+    if no_newline {
+        print!("{}", text.trim_end_matches('\n'));
+    } else {
+        print!("{text}");
+    }
+}
+
+/// Whether each field holds only numbers, such as `port`, so it can be
+/// right-aligned. Fields with no values at all are not numeric.
+pub(crate) fn numeric_columns<R: Borrow<UrlRecord>>(records: &[R], fields: &[&str]) -> Vec<bool> {
+    fields
+        .iter()
+        .map(|field| {
+            let mut values = records
+                .iter()
+                .filter_map(|record| record.borrow().get_field(field))
+                .peekable();
+            values.peek().is_some() && values.all(is_number)
+        })
+        .collect()
+}
+
+pub(crate) fn is_number(value: &str) -> bool {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, "0"));
+    !whole.is_empty()
+        && whole.bytes().all(|b| b.is_ascii_digit())
+        && !fraction.is_empty()
+        && fraction.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// How `print_tabular` separates and escapes values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tabular {
+    /// RFC 4180 CSV: values containing the delimiter, the quote character or
+    /// a line break are quoted, with quote characters doubled.
+    Csv { delimiter: char, quote: char },
+    /// Tab-separated values with tabs, line breaks and backslashes escaped as
+    /// `\t`, `\n`, `\r` and `\\`, so every record stays on one line.
+    Tsv,
+}
+
+impl Tabular {
+    fn separator(&self) -> char {
+        match self {
+            Tabular::Csv { delimiter, .. } => *delimiter,
+            Tabular::Tsv => '\t',
+        }
+    }
+
+    fn escape(&self, value: &str) -> String {
+        match self {
+            Tabular::Csv { delimiter, quote } => csv_quote(value, *delimiter, *quote),
+            Tabular::Tsv => tsv_escape(value),
+        }
+    }
+}
+
+/// Prints records as CSV or TSV. The `null_value` for missing fields is
+/// written as is, so a TSV `\N` is not mistaken for an escaped backslash.
+/// With `bom`, output starts with a UTF-8 byte order mark so spreadsheet
+/// applications such as Excel detect the encoding.
 pub fn print_tabular(
     records: &[UrlRecord],
     fields: &[&str],
     header: bool,
-    separator: char,
+    tabular: Tabular,
+    bom: bool,
     null_value: &str,
     no_newline: bool,
 ) {
+    let separator = tabular.separator().to_string();
+    let null_value = match tabular {
+        Tabular::Csv { .. } => tabular.escape(null_value),
+        Tabular::Tsv => null_value.to_string(),
+    };
+    if bom {
+        print!("\u{feff}");
+    }
     if header {
-        let header_line = fields.join(&separator.to_string());
-        println!("{header_line}");
+        let names: Vec<String> = fields.iter().map(|field| tabular.escape(field)).collect();
+        println!("{}", names.join(&separator));
     }
 
     for (i, record) in records.iter().enumerate() {
-        let row = select_fields(record, fields, null_value);
-        let line = row.join(&separator.to_string());
+        let row: Vec<String> = fields
+            .iter()
+            .map(|field| match record.get_field(field) {
+                Some(value) => tabular.escape(value),
+                None => null_value.clone(),
+            })
+            .collect();
+        let line = row.join(&separator);
         if no_newline && i == records.len() - 1 {
             print!("{line}");
         } else {
@@ -178,9 +688,154 @@ pub fn print_tabular(
     }
 }
 
+/// Fields written as JSON integers by `--typed` output when they hold one.
+pub const INTEGER_FIELDS: &[&str] = &[
+    "port",
+    "line",
+    "offset",
+    "status",
+    "bytes",
+    "risk_score",
+    "edit_distance",
+];
+
+/// Fields written as JSON booleans by `--typed` output.
+pub const BOOLEAN_FIELDS: &[&str] = &["mixed_script", "in_scope"];
+
+/// Fields whose values come from the input and so are not guaranteed to be
+/// numeric; typed output keeps them as strings when they are not.
+const UNCHECKED_INTEGER_FIELDS: &[&str] = &["status", "bytes"];
+
+/// Builds the JSON object for one record. Without `typed`, every present
+/// field is a string and missing fields are left out. With `typed`, numeric
+/// and boolean fields get their JSON types, `path` becomes an array of
+/// segments, `query` an array of `[key, value]` pairs in URL order and
+/// missing fields are written as `null`.
+pub(crate) fn json_object(record: &UrlRecord, fields: &[&str], typed: bool) -> serde_json::Value {
+    let mut map = serde_json::Map::new();
+    for field in fields {
+        match record.get_field(field) {
+            Some(value) if typed => {
+                map.insert(field.to_string(), typed_value(field, value));
+            }
+            Some(value) => {
+                map.insert(
+                    field.to_string(),
+                    serde_json::Value::String(value.to_string()),
+                );
+            }
+            None if typed => {
+                map.insert(field.to_string(), serde_json::Value::Null);
+            }
+            None => {}
+        }
+    }
+    serde_json::Value::Object(map)
+}
+
+fn typed_value(field: &str, value: &str) -> serde_json::Value {
+    use serde_json::Value;
+
+    if INTEGER_FIELDS.contains(&field) {
+        if let Ok(number) = value.parse::<i64>() {
+            return Value::from(number);
+        }
+    }
+    if BOOLEAN_FIELDS.contains(&field) {
+        if let Ok(flag) = value.parse::<bool>() {
+            return Value::Bool(flag);
+        }
+    }
+    match field {
+        "path" => path_segments(value),
+        "query" => query_params(value),
+        _ => Value::String(value.to_string()),
+    }
+}
+
+/// Splits a path into its segments; `/a/b/` becomes `["a", "b", ""]`, so the
+/// path can be rebuilt exactly by joining them after a leading `/`.
+pub fn path_segments(path: &str) -> serde_json::Value {
+    let path = path.strip_prefix('/').unwrap_or(path);
+    path.split('/').map(serde_json::Value::from).collect()
+}
+
+/// Turns a query string into an array of `[key, value]` pairs, in the order
+/// they appear so that no value or ordering is lost. Values are kept
+/// percent-encoded as they appear in the URL; a parameter without `=` has a
+/// `null` value.
+pub fn query_params(query: &str) -> serde_json::Value {
+    use serde_json::Value;
+
+    let query = query.strip_prefix('?').unwrap_or(query);
+    query
+        .split('&')
+        .filter(|param| !param.is_empty())
+        .map(|param| {
+            let (key, value) = match param.split_once('=') {
+                Some((key, value)) => (key, Value::from(value)),
+                None => (param, Value::Null),
+            };
+            Value::Array(vec![Value::from(key), value])
+        })
+        .collect()
+}
+
+/// Describes the records written by `--format json` and `--format jsonl`
+/// for `fields` as a JSON Schema (draft 2020-12).
+pub fn json_schema(fields: &[&str], typed: bool) -> serde_json::Value {
+    use serde_json::json;
+
+    let mut properties = serde_json::Map::new();
+    for field in fields {
+        let schema = if !typed {
+            json!({ "type": "string" })
+        } else if *field == "port" {
+            json!({ "type": ["integer", "null"], "minimum": 1, "maximum": 65535 })
+        } else if UNCHECKED_INTEGER_FIELDS.contains(field) {
+            json!({ "type": ["integer", "string", "null"] })
+        } else if INTEGER_FIELDS.contains(field) {
+            json!({ "type": ["integer", "null"] })
+        } else if BOOLEAN_FIELDS.contains(field) {
+            json!({ "type": ["boolean", "null"] })
+        } else if *field == "path" {
+            json!({ "type": ["array", "null"], "items": { "type": "string" } })
+        } else if *field == "query" {
+            json!({
+                "type": ["array", "null"],
+                "items": {
+                    "type": "array",
+                    "prefixItems": [
+                        { "type": "string" },
+                        { "type": ["string", "null"] }
+                    ],
+                    "minItems": 2,
+                    "maxItems": 2
+                }
+            })
+        } else {
+            json!({ "type": ["string", "null"] })
+        };
+        properties.insert(field.to_string(), schema);
+    }
+
+    let mut schema = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "rexturl record",
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": false,
+    });
+    if typed {
+        schema["required"] = json!(fields);
+    }
+    schema
+}
+
 pub fn print_json(
     records: &[UrlRecord],
     fields: &[&str],
+    typed: bool,
     pretty: bool,
     no_newline: bool,
 ) -> Result<(), serde_json::Error> {
@@ -191,18 +846,7 @@ pub fn print_json(
 
     let urls: Vec<serde_json::Value> = records
         .iter()
-        .map(|record| {
-            let mut map = serde_json::Map::new();
-            for field in fields {
-                if let Some(value) = record.get_field(field) {
-                    map.insert(
-                        field.to_string(),
-                        serde_json::Value::String(value.to_string()),
-                    );
-                }
-            }
-            serde_json::Value::Object(map)
-        })
+        .map(|record| json_object(record, fields, typed))
         .collect();
 
     let wrapper = UrlsWrapper { urls };
@@ -225,20 +869,11 @@ pub fn print_json(
 pub fn print_jsonl(
     records: &[UrlRecord],
     fields: &[&str],
+    typed: bool,
     no_newline: bool,
 ) -> Result<(), serde_json::Error> {
     for (i, record) in records.iter().enumerate() {
-        let mut map = serde_json::Map::new();
-        for field in fields {
-            if let Some(value) = record.get_field(field) {
-                map.insert(
-                    field.to_string(),
-                    serde_json::Value::String(value.to_string()),
-                );
-            }
-        }
-
-        let line = serde_json::to_string(&serde_json::Value::Object(map))?;
+        let line = serde_json::to_string(&json_object(record, fields, typed))?;
         if no_newline && i == records.len() - 1 {
             print!("{line}");
         } else {
@@ -270,32 +905,77 @@ pub fn print_custom(
     Ok(())
 }
 
+/// Settings for `--format sql`.
+#[derive(Debug, Clone, Copy)]
+pub struct SqlOptions<'a> {
+    pub table: &'a str,
+    pub dialect: SqlDialect,
+    pub create_table: bool,
+    /// Index the `domain` and `hostname` columns in `CREATE TABLE` output.
+    pub indexes: bool,
+    /// Rows per `INSERT` statement.
+    pub batch_size: usize,
+}
+
+/// Prints records as multi-row `INSERT` statements of up to
+/// `options.batch_size` rows, wrapped in a single transaction. The
+/// `CREATE TABLE` statement, when requested, comes before the transaction
+/// because MySQL commits implicitly on DDL.
 pub fn print_sql(
     records: &[UrlRecord],
     fields: &[&str],
-    table_name: &str,
-    dialect: SqlDialect,
-    create_table: bool,
+    options: SqlOptions,
     no_newline: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
     if fields.is_empty() {
         return Err("SQL format requires at least one field to be specified".into());
     }
-
-    if create_table {
-        let create_sql = generate_create_table(table_name, fields, dialect);
-        println!("{create_sql}");
+    if options.batch_size == 0 {
+        return Err("SQL batch size must be at least 1".into());
     }
 
-    for (i, record) in records.iter().enumerate() {
-        let insert_sql = generate_insert_statement(record, fields, table_name, dialect);
+    let table = options.dialect.quote_table_name(options.table)?;
+    let create_table = if options.create_table {
+        Some(generate_create_table(
+            options.table,
+            fields,
+            options.dialect,
+            options.indexes,
+        )?)
+    } else {
+        None
+    };
 
-        if no_newline && i == records.len() - 1 {
-            print!("{insert_sql}");
-        } else {
-            println!("{insert_sql}");
+    // Statements are written as they are generated, so a large dump is never
+    // held in memory as a whole.
+    let mut out = io::BufWriter::new(io::stdout().lock());
+    let mut started = false;
+    let mut write_statement = |statement: &str| -> io::Result<()> {
+        if started {
+            out.write_all(b"\n")?;
+        }
+        started = true;
+        out.write_all(statement.as_bytes())
+    };
+    if let Some(create_table) = create_table {
+        write_statement(&create_table)?;
+    }
+    if !records.is_empty() {
+        write_statement(options.dialect.begin_transaction())?;
+        for batch in records.chunks(options.batch_size) {
+            write_statement(&generate_insert_statement(
+                batch,
+                fields,
+                &table,
+                options.dialect,
+            ))?;
         }
+        write_statement("COMMIT;")?;
+    }
+    if started && !no_newline {
+        out.write_all(b"\n")?;
     }
+    out.flush()?;
 
     Ok(())
 }
@@ -332,7 +1012,7 @@ fn parse_template(template: &str) -> Result<Vec<TemplateToken>, Box<dyn std::err
             let mut field_spec = String::new();
             let mut brace_count = 1;
 
-            while let Some(ch) = chars.next() {
+            for ch in chars.by_ref() {
                 if ch == '{' {
                     brace_count += 1;
                     field_spec.push(ch);
@@ -387,8 +1067,11 @@ fn parse_field_spec(spec: &str) -> Result<TemplateToken, Box<dyn std::error::Err
         field_name = spec.to_string();
     }
 
-    if !is_valid_field_name(&field_name) {
-        return Err(format!("Invalid field name: {field_name}").into());
+    // Any name is accepted, as with --fields: pass-through columns and
+    // annotations are looked up among the record's extra fields, and a field
+    // the record lacks renders as missing.
+    if field_name.trim().is_empty() {
+        return Err("Empty field name in template".into());
     }
 
     Ok(TemplateToken {
@@ -401,21 +1084,11 @@ fn parse_field_spec(spec: &str) -> Result<TemplateToken, Box<dyn std::error::Err
     })
 }
 
-fn is_valid_field_name(name: &str) -> bool {
-    matches!(
-        name,
-        "url"
-            | "scheme"
-            | "username"
-            | "host"
-            | "hostname"
-            | "subdomain"
-            | "domain"
-            | "port"
-            | "path"
-            | "query"
-            | "fragment"
-    )
+pub fn template_fields(template: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    Ok(parse_template(template)?
+        .into_iter()
+        .filter_map(|token| token.field_name)
+        .collect())
 }
 
 fn render_template(
@@ -474,13 +1147,62 @@ fn shell_escape(value: &str) -> String {
 }
 
 fn csv_escape(value: &str) -> String {
-    if value.contains(',') || value.contains('"') || value.contains('\n') {
-        format!("\"{}\"", value.replace('"', "\"\""))
+    csv_quote(value, ',', '"')
+}
+
+/// Quotes a CSV value when it contains the delimiter, the quote character or
+/// a line break, doubling any quote characters inside it.
+pub fn csv_quote(value: &str, delimiter: char, quote: char) -> String {
+    if value.contains([delimiter, quote, '\n', '\r']) {
+        let doubled = value.replace(quote, &format!("{quote}{quote}"));
+        format!("{quote}{doubled}{quote}")
     } else {
         value.to_string()
     }
 }
 
+/// Escapes the characters that would break a TSV row.
+pub fn tsv_escape(value: &str) -> String {
+    if !value.contains(['\t', '\n', '\r', '\\']) {
+        return value.to_string();
+    }
+    let mut escaped = String::with_capacity(value.len() + 2);
+    for c in value.chars() {
+        match c {
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\\' => escaped.push_str("\\\\"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Reverses [`tsv_escape`]. Unknown escapes are kept as written.
+pub fn tsv_unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some('\\') => unescaped.push('\\'),
+            Some(other) => {
+                unescaped.push('\\');
+                unescaped.push(other);
+            }
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
 fn json_escape(value: &str) -> String {
     serde_json::to_string(value).unwrap_or_else(|_| "null".to_string())
 }
@@ -489,10 +1211,37 @@ fn sql_escape(value: &str) -> String {
     format!("'{}'", value.replace('\'', "''"))
 }
 
-fn generate_create_table(table_name: &str, fields: &[&str], dialect: SqlDialect) -> String {
-    let mut sql = format!("CREATE TABLE IF NOT EXISTS {} (\n", table_name);
-    sql.push_str("    id SERIAL PRIMARY KEY,\n");
+/// Columns `CREATE TABLE` adds around the selected fields.
+const GENERATED_COLUMNS: [&str; 2] = ["id", "created_at"];
+
+/// Fields `--sql-indexes` indexes when they are selected.
+const INDEXED_FIELDS: [&str; 2] = ["domain", "hostname"];
+
+fn generate_create_table(
+    table_name: &str,
+    fields: &[&str],
+    dialect: SqlDialect,
+    indexes: bool,
+) -> Result<String, String> {
+    if let Some(field) = fields
+        .iter()
+        .find(|field| GENERATED_COLUMNS.contains(field))
+    {
+        return Err(format!(
+            "field {field:?} clashes with the {field} column CREATE TABLE adds"
+        ));
+    }
+    let table = dialect.quote_table_name(table_name)?;
+    let indexed: Vec<&str> = INDEXED_FIELDS
+        .into_iter()
+        .filter(|field| indexes && fields.contains(field))
+        .collect();
 
+    let mut columns = vec![format!(
+        "{} {}",
+        dialect.quote_identifier("id"),
+        dialect.id_column()
+    )];
     for field in fields {
         let column_type = match dialect {
             SqlDialect::Postgres => get_postgres_column_type(field),
@@ -500,14 +1249,58 @@ fn generate_create_table(table_name: &str, fields: &[&str], dialect: SqlDialect)
             SqlDialect::Sqlite => get_sqlite_column_type(field),
             SqlDialect::Generic => get_generic_column_type(field),
         };
-
-        sql.push_str(&format!("    {} {},\n", field, column_type));
+        columns.push(format!(
+            "{} {}",
+            dialect.quote_identifier(field),
+            column_type
+        ));
+    }
+    columns.push(format!(
+        "{} TIMESTAMP DEFAULT CURRENT_TIMESTAMP",
+        dialect.quote_identifier("created_at")
+    ));
+
+    // Index names are unqualified: an index lives in its table's schema.
+    let (schema, bare_table) = match table_name.split_once('.') {
+        Some((schema, bare_table)) => (Some(schema), bare_table),
+        None => (None, table_name),
+    };
+    let index_name = |field: &str| dialect.quote_identifier(&format!("{bare_table}_{field}_idx"));
+
+    // MySQL has no CREATE INDEX IF NOT EXISTS, so its indexes are declared
+    // inline, which CREATE TABLE IF NOT EXISTS already makes idempotent.
+    if dialect == SqlDialect::Mysql {
+        for field in &indexed {
+            columns.push(format!(
+                "INDEX {} ({})",
+                index_name(field),
+                dialect.quote_identifier(field)
+            ));
+        }
     }
 
-    sql.push_str("    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP\n");
-    sql.push_str(");");
+    let mut sql = format!(
+        "CREATE TABLE IF NOT EXISTS {table} (\n    {}\n);",
+        columns.join(",\n    ")
+    );
+    if dialect != SqlDialect::Mysql {
+        for field in &indexed {
+            // SQLite qualifies the index rather than the table.
+            let (index, on) = match (dialect, schema) {
+                (SqlDialect::Sqlite, Some(schema)) => (
+                    format!("{}.{}", dialect.quote_identifier(schema), index_name(field)),
+                    dialect.quote_identifier(bare_table),
+                ),
+                _ => (index_name(field), table.clone()),
+            };
+            sql.push_str(&format!(
+                "\nCREATE INDEX IF NOT EXISTS {index} ON {on} ({});",
+                dialect.quote_identifier(field)
+            ));
+        }
+    }
 
-    sql
+    Ok(sql)
 }
 
 fn get_postgres_column_type(field: &str) -> &'static str {
@@ -553,31 +1346,80 @@ fn get_generic_column_type(field: &str) -> &'static str {
 }
 
 fn generate_insert_statement(
-    record: &UrlRecord,
+    records: &[UrlRecord],
     fields: &[&str],
     table_name: &str,
-    _dialect: SqlDialect,
+    dialect: SqlDialect,
 ) -> String {
-    let field_names = fields.join(", ");
-    let values: Vec<String> = fields
+    let columns: Vec<String> = fields
         .iter()
-        .map(|field| {
-            if let Some(value) = record.get_field(field) {
-                sql_escape(value)
-            } else {
-                "NULL".to_string()
-            }
+        .map(|field| dialect.quote_identifier(field))
+        .collect();
+    let rows: Vec<String> = records
+        .iter()
+        .map(|record| {
+            let values: Vec<String> = fields
+                .iter()
+                .map(|field| match record.get_field(field) {
+                    Some(value) => dialect.quote_string(value),
+                    None => "NULL".to_string(),
+                })
+                .collect();
+            format!("    ({})", values.join(", "))
         })
         .collect();
 
     format!(
-        "INSERT INTO {} ({}) VALUES ({});",
+        "INSERT INTO {} ({}) VALUES\n{};",
         table_name,
-        field_names,
-        values.join(", ")
+        columns.join(", "),
+        rows.join(",\n")
     )
 }
 
+/// Assembles a URL from component values looked up by field name. The host
+/// is taken from `host` or `hostname`, or else joined from `subdomain` and
+/// `domain`; a scheme and a host are required and everything else is
+/// optional. Empty values count as missing.
+pub fn assemble_url<'a, F>(get: F) -> Result<String, String>
+where
+    F: Fn(&str) -> Option<&'a str>,
+{
+    let get = |name: &str| get(name).map(str::trim).filter(|value| !value.is_empty());
+    let scheme = get("scheme").ok_or("missing scheme")?;
+    let host = match get("host").or_else(|| get("hostname")) {
+        Some(host) => host.to_string(),
+        None => match (get("subdomain"), get("domain").ok_or("missing host")?) {
+            (Some(subdomain), domain) => format!("{subdomain}.{domain}"),
+            (None, domain) => domain.to_string(),
+        },
+    };
+    let port = match get("port") {
+        Some(port) => Some(
+            port.parse::<u16>()
+                .ok()
+                .filter(|&p| p != 0)
+                .ok_or_else(|| format!("invalid port {port:?}"))?,
+        ),
+        None => None,
+    };
+
+    let parts = UrlParts {
+        scheme: scheme.to_string(),
+        username: get("username").map(str::to_string),
+        password: get("password").map(str::to_string),
+        host,
+        port,
+        path: get("path").unwrap_or("/").to_string(),
+        query: get("query").map(|q| q.trim_start_matches('?').to_string()),
+        fragment: get("fragment").map(|f| f.trim_start_matches('#').to_string()),
+    };
+    let url = parts
+        .to_url()
+        .map_err(|e| format!("cannot assemble URL: {e}"))?;
+    Ok(url.as_str().to_string())
+}
+
 pub fn to_record(input: &str) -> Result<UrlRecord, crate::UrlParseError> {
     let url = parse_url(input)?;
     let components = extract_url_components(&url);
@@ -608,6 +1450,7 @@ pub fn to_record(input: &str) -> Result<UrlRecord, crate::UrlParseError> {
         path,
         query: non_empty_string(components.query),
         fragment: non_empty_string(components.fragment),
+        extra: BTreeMap::new(),
     })
 }
 
@@ -628,6 +1471,7 @@ mod tests {
             path: Some("/path".to_string()),
             query: None,
             fragment: None,
+            extra: BTreeMap::new(),
         }
     }
 
@@ -644,6 +1488,7 @@ mod tests {
             path: Some("/v1/users".to_string()),
             query: Some("limit=10".to_string()),
             fragment: Some("results".to_string()),
+            extra: BTreeMap::new(),
         }
     }
 
@@ -656,6 +1501,11 @@ mod tests {
         assert_eq!("jsonl".parse::<Format>().unwrap(), Format::Jsonl);
         assert_eq!("custom".parse::<Format>().unwrap(), Format::Custom);
         assert_eq!("sql".parse::<Format>().unwrap(), Format::Sql);
+        assert_eq!("table".parse::<Format>().unwrap(), Format::Table);
+        assert_eq!("markdown".parse::<Format>().unwrap(), Format::Markdown);
+        assert_eq!("html".parse::<Format>().unwrap(), Format::Html);
+        assert_eq!("xml".parse::<Format>().unwrap(), Format::Xml);
+        assert_eq!("yaml".parse::<Format>().unwrap(), Format::Yaml);
         assert!("invalid".parse::<Format>().is_err());
     }
 
@@ -668,6 +1518,183 @@ mod tests {
         assert_eq!(record.get_field("unknown"), None);
     }
 
+    #[test]
+    fn test_url_record_extra_fields() {
+        let mut record = create_test_record();
+        record.set_extra("risk_score", "25");
+        assert_eq!(record.get_field("risk_score"), Some("25"));
+        assert_eq!(record.get_field("domain"), Some("example.com"));
+    }
+
+    #[test]
+    fn test_query_params() {
+        use serde_json::json;
+
+        assert_eq!(
+            query_params("?b=1&a=&c"),
+            json!([["b", "1"], ["a", ""], ["c", null]])
+        );
+        assert_eq!(
+            query_params("?tag=a&tag=b%20c"),
+            json!([["tag", "a"], ["tag", "b%20c"]])
+        );
+        assert_eq!(query_params("?"), json!([]));
+    }
+
+    #[test]
+    fn test_path_segments() {
+        use serde_json::json;
+
+        assert_eq!(path_segments("/"), json!([""]));
+        assert_eq!(path_segments("/a/b/"), json!(["a", "b", ""]));
+        assert_eq!(path_segments("/a%2Fb/c"), json!(["a%2Fb", "c"]));
+    }
+
+    #[test]
+    fn test_typed_json_object() {
+        use serde_json::json;
+
+        let mut record = to_record("https://example.com:8080/a/b?x=1&x=2").unwrap();
+        record.set_extra("mixed_script", "false");
+        record.set_extra("status", "-");
+        let fields = [
+            "port",
+            "path",
+            "query",
+            "fragment",
+            "mixed_script",
+            "status",
+        ];
+
+        assert_eq!(
+            json_object(&record, &fields, true),
+            json!({
+                "port": 8080,
+                "path": ["a", "b"],
+                "query": [["x", "1"], ["x", "2"]],
+                "fragment": null,
+                "mixed_script": false,
+                "status": "-"
+            })
+        );
+        assert_eq!(
+            json_object(&record, &fields, false),
+            json!({
+                "port": "8080",
+                "path": "/a/b",
+                "query": "?x=1&x=2",
+                "mixed_script": "false",
+                "status": "-"
+            })
+        );
+    }
+
+    #[test]
+    fn test_typed_json_deserializes_back() {
+        let record = to_record("https://example.com:8080/a/b/?x=1&y&x=2").unwrap();
+        let fields = ["url", "port", "path", "query", "fragment"];
+        let typed = json_object(&record, &fields, true);
+        let read: UrlRecord = serde_json::from_value(typed).unwrap();
+
+        assert_eq!(read.port.as_deref(), Some("8080"));
+        assert_eq!(read.path.as_deref(), Some("/a/b/"));
+        assert_eq!(read.query.as_deref(), Some("?x=1&y&x=2"));
+        assert_eq!(read.fragment, None);
+
+        let read: UrlRecord =
+            serde_json::from_str(r#"{"query": {"a": "1", "n": 2}, "path": "/x"}"#).unwrap();
+        assert_eq!(read.query.as_deref(), Some("?a=1&n=2"));
+        assert!(serde_json::from_str::<UrlRecord>(r#"{"path": [1]}"#).is_err());
+    }
+
+    #[test]
+    fn test_json_schema() {
+        let schema = json_schema(&["url", "port", "query"], true);
+        assert_eq!(schema["properties"]["port"]["type"][0], "integer");
+        assert_eq!(schema["properties"]["url"]["type"][0], "string");
+        assert_eq!(schema["properties"]["query"]["type"][0], "array");
+        assert_eq!(
+            schema["required"],
+            serde_json::json!(["url", "port", "query"])
+        );
+
+        let schema = json_schema(&["port"], false);
+        assert_eq!(schema["properties"]["port"]["type"], "string");
+        assert!(schema.get("required").is_none());
+    }
+
+    #[test]
+    fn test_render_table() {
+        let mut first = to_record("https://example.com:8080/a").unwrap();
+        first.set_extra("priority", "0.5");
+        let second = to_record("https://a-very-long-hostname.example.org/").unwrap();
+        let fields = ["hostname", "port", "priority"];
+
+        assert_eq!(
+            render_table(
+                &[first.clone(), second.clone()],
+                &fields,
+                TableStyle::Unicode,
+                30,
+                "-"
+            ),
+            "┌────────────────────────────────┬──────┬──────────┐\n\
+             │ hostname                       │ port │ priority │\n\
+             ├────────────────────────────────┼──────┼──────────┤\n\
+             │ example.com                    │ 8080 │      0.5 │\n\
+             │ a-very-long-hostname.example.… │    - │        - │\n\
+             └────────────────────────────────┴──────┴──────────┘\n"
+        );
+
+        assert_eq!(
+            render_table(&[first, second], &fields, TableStyle::Ascii, 12, "-"),
+            "+--------------+------+----------+\n\
+             | hostname     | port | priority |\n\
+             +--------------+------+----------+\n\
+             | example.com  | 8080 |      0.5 |\n\
+             | a-very-lo... |    - |        - |\n\
+             +--------------+------+----------+\n"
+        );
+
+        let mut record = to_record("https://example.jp/").unwrap();
+        record.set_extra("name", "例え");
+        record.set_extra("note", "a\tb\u{1b}");
+        assert_eq!(
+            render_table(
+                &[record.clone()],
+                &["name", "note"],
+                TableStyle::Ascii,
+                0,
+                "-"
+            ),
+            "+------+------------+\n\
+             | name | note       |\n\
+             +------+------------+\n\
+             | 例え | a\\tb\\u{1b} |\n\
+             +------+------------+\n"
+        );
+
+        record.set_extra("name", "例え例え");
+        assert_eq!(
+            render_table(&[record], &["name"], TableStyle::Ascii, 5, "-"),
+            "+-------+\n\
+             | name  |\n\
+             +-------+\n\
+             | 例... |\n\
+             +-------+\n"
+        );
+    }
+
+    #[test]
+    fn test_is_number() {
+        assert!(is_number("8080"));
+        assert!(is_number("-1.5"));
+        assert!(!is_number("1."));
+        assert!(!is_number("inf"));
+        assert!(!is_number("?q=1"));
+        assert!(!is_number(""));
+    }
+
     #[test]
     fn test_select_fields() {
         let record = create_test_record();
@@ -718,10 +1745,10 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_template_invalid_field() {
-        let template = "{invalid_field}";
-        let result = parse_template(template);
-        assert!(result.is_err());
+    fn test_parse_template_extra_and_empty_fields() {
+        let tokens = parse_template("{owner}").unwrap();
+        assert_eq!(tokens[0].field_name.as_deref(), Some("owner"));
+        assert!(parse_template("{}").is_err());
     }
 
     #[test]
@@ -771,6 +1798,24 @@ mod tests {
         assert_eq!(csv_escape("with\nnewline"), "\"with\nnewline\"");
     }
 
+    #[test]
+    fn test_csv_quote_with_custom_delimiter_and_quote() {
+        assert_eq!(csv_quote("a,b", ';', '\''), "a,b");
+        assert_eq!(csv_quote("a;b", ';', '\''), "'a;b'");
+        assert_eq!(csv_quote("it's", ';', '\''), "'it''s'");
+        assert_eq!(csv_quote("line\r\nbreak", ',', '"'), "\"line\r\nbreak\"");
+    }
+
+    #[test]
+    fn test_tsv_escape_round_trips() {
+        assert_eq!(tsv_escape("plain"), "plain");
+        assert_eq!(tsv_escape("a\tb\nc\\d"), "a\\tb\\nc\\\\d");
+        for value in ["a\tb\nc\\d\r", "C:\\new", "\\N", ""] {
+            assert_eq!(tsv_unescape(&tsv_escape(value)), value);
+        }
+        assert_eq!(tsv_unescape("\\x"), "\\x");
+    }
+
     #[test]
     fn test_sql_escape() {
         assert_eq!(sql_escape("simple"), "'simple'");
@@ -780,27 +1825,107 @@ mod tests {
     #[test]
     fn test_generate_create_table() {
         let fields = vec!["domain", "path", "port"];
-        let sql = generate_create_table("test_table", &fields, SqlDialect::Postgres);
+        let sql =
+            generate_create_table("test_table", &fields, SqlDialect::Postgres, false).unwrap();
+
+        assert!(sql.contains("CREATE TABLE IF NOT EXISTS \"test_table\""));
+        assert!(sql.contains("\"id\" BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY"));
+        assert!(sql.contains("\"domain\" VARCHAR(253)"));
+        assert!(sql.contains("\"path\" TEXT"));
+        assert!(sql.contains("\"port\" INTEGER"));
+        assert!(sql.contains("\"created_at\" TIMESTAMP"));
+        assert!(!sql.contains("CREATE INDEX"));
+    }
 
-        assert!(sql.contains("CREATE TABLE IF NOT EXISTS test_table"));
-        assert!(sql.contains("domain VARCHAR(253)"));
-        assert!(sql.contains("path TEXT"));
-        assert!(sql.contains("port INTEGER"));
-        assert!(sql.contains("created_at TIMESTAMP"));
+    #[test]
+    fn test_generate_create_table_dialects() {
+        let fields = vec!["domain", "hostname"];
+
+        assert_eq!(
+            generate_create_table("crawl.urls", &fields, SqlDialect::Postgres, true).unwrap(),
+            "CREATE TABLE IF NOT EXISTS \"crawl\".\"urls\" (\n    \
+             \"id\" BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,\n    \
+             \"domain\" VARCHAR(253),\n    \
+             \"hostname\" VARCHAR(253),\n    \
+             \"created_at\" TIMESTAMP DEFAULT CURRENT_TIMESTAMP\n\
+             );\n\
+             CREATE INDEX IF NOT EXISTS \"urls_domain_idx\" ON \"crawl\".\"urls\" (\"domain\");\n\
+             CREATE INDEX IF NOT EXISTS \"urls_hostname_idx\" ON \"crawl\".\"urls\" (\"hostname\");"
+        );
+        assert_eq!(
+            generate_create_table("urls", &["domain", "path"], SqlDialect::Mysql, true).unwrap(),
+            "CREATE TABLE IF NOT EXISTS `urls` (\n    \
+             `id` BIGINT AUTO_INCREMENT PRIMARY KEY,\n    \
+             `domain` VARCHAR(253),\n    \
+             `path` TEXT,\n    \
+             `created_at` TIMESTAMP DEFAULT CURRENT_TIMESTAMP,\n    \
+             INDEX `urls_domain_idx` (`domain`)\n\
+             );"
+        );
+
+        let sqlite = generate_create_table("main.urls", &fields, SqlDialect::Sqlite, true).unwrap();
+        assert!(sqlite.contains("\"id\" INTEGER PRIMARY KEY AUTOINCREMENT"));
+        assert!(sqlite.contains(
+            "CREATE INDEX IF NOT EXISTS \"main\".\"urls_domain_idx\" ON \"urls\" (\"domain\");"
+        ));
+
+        assert!(generate_create_table("urls", &["id"], SqlDialect::Sqlite, false).is_err());
+    }
+
+    #[test]
+    fn test_quote_table_name() {
+        assert_eq!(
+            SqlDialect::Postgres
+                .quote_table_name("public.urls")
+                .unwrap(),
+            "\"public\".\"urls\""
+        );
+        assert_eq!(
+            SqlDialect::Mysql.quote_table_name("_urls2").unwrap(),
+            "`_urls2`"
+        );
+        for name in [
+            "",
+            "urls; DROP TABLE x",
+            "a.b.c",
+            "1urls",
+            "u\"rls",
+            "urls.",
+        ] {
+            assert!(
+                SqlDialect::Postgres.quote_table_name(name).is_err(),
+                "{name}"
+            );
+        }
     }
 
     #[test]
     fn test_generate_insert_statement() {
         let record = create_test_record();
         let fields = vec!["domain", "path", "port"];
-        let sql = generate_insert_statement(&record, &fields, "test_table", SqlDialect::Postgres);
+        let sql = generate_insert_statement(
+            &[record.clone(), record],
+            &fields,
+            "\"test_table\"",
+            SqlDialect::Postgres,
+        );
 
         assert_eq!(
             sql,
-            "INSERT INTO test_table (domain, path, port) VALUES ('example.com', '/path', NULL);"
+            "INSERT INTO \"test_table\" (\"domain\", \"path\", \"port\") VALUES\n    \
+             ('example.com', '/path', NULL),\n    \
+             ('example.com', '/path', NULL);"
         );
     }
 
+    #[test]
+    fn test_sql_dialect_quoting() {
+        assert_eq!(SqlDialect::Postgres.quote_identifier("a\"b"), "\"a\"\"b\"");
+        assert_eq!(SqlDialect::Mysql.quote_identifier("a`b"), "`a``b`");
+        assert_eq!(SqlDialect::Sqlite.quote_string("a\\'b"), "'a\\''b'");
+        assert_eq!(SqlDialect::Mysql.quote_string("a\\'b\0"), "'a\\\\''b\\0'");
+    }
+
     #[test]
     fn test_mysql_column_types() {
         assert_eq!(get_mysql_column_type("port"), "INT");
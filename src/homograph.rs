@@ -0,0 +1,117 @@
+use std::path::Path;
+
+use crate::domain::{
+    extract_domain, host_skeleton, is_mixed_script, read_domain_list, to_unicode_host,
+};
+use crate::error::AppError;
+use crate::formatter::UrlRecord;
+
+pub const HOMOGRAPH_FIELDS: &[&str] = &["unicode_host", "mixed_script", "homograph_of"];
+
+/// Brand domains whose confusable skeletons are checked against every host.
+#[derive(Debug, Clone, Default)]
+pub struct ProtectList {
+    entries: Vec<(String, String)>,
+}
+
+impl ProtectList {
+    pub fn from_domains<I, S>(domains: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let entries = domains
+            .into_iter()
+            .map(|domain| to_unicode_host(domain.as_ref().trim()))
+            .filter(|domain| !domain.is_empty())
+            .map(|domain| {
+                let skeleton = host_skeleton(&domain);
+                (domain, skeleton)
+            })
+            .collect();
+        Self { entries }
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, AppError> {
+        Ok(Self::from_domains(read_domain_list(path)?))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the protected domain that `host` imitates, if any. A host whose
+    /// registrable domain is the protected domain itself is not a lookalike.
+    pub fn find_lookalike(&self, host: &str) -> Option<&str> {
+        let domain = extract_domain(&to_unicode_host(host));
+        if domain.is_empty() {
+            return None;
+        }
+
+        let skeleton = host_skeleton(&domain);
+        self.entries
+            .iter()
+            .find(|(protected, protected_skeleton)| {
+                *protected_skeleton == skeleton && *protected != domain
+            })
+            .map(|(protected, _)| protected.as_str())
+    }
+}
+
+pub fn annotate_record(record: &mut UrlRecord, protect_list: Option<&ProtectList>) {
+    let Some(host) = record.hostname.clone() else {
+        return;
+    };
+
+    record.set_extra("unicode_host", to_unicode_host(&host));
+    record.set_extra("mixed_script", is_mixed_script(&host).to_string());
+    if let Some(protected) = protect_list.and_then(|list| list.find_lookalike(&host)) {
+        record.set_extra("homograph_of", protected);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formatter::to_record;
+
+    #[test]
+    fn test_find_lookalike() {
+        let list = ProtectList::from_domains(["paypal.com", "example.org"]);
+        assert_eq!(list.len(), 2);
+
+        assert_eq!(
+            list.find_lookalike("\u{440}\u{430}ypal.com"),
+            Some("paypal.com")
+        );
+        assert_eq!(
+            list.find_lookalike("login.xn--ypal-43d9g.com"),
+            Some("paypal.com")
+        );
+        assert_eq!(list.find_lookalike("www.paypal.com"), None);
+        assert_eq!(list.find_lookalike("example.com"), None);
+    }
+
+    #[test]
+    fn test_annotate_record() {
+        let list = ProtectList::from_domains(["paypal.com"]);
+        let mut record = to_record("https://xn--ypal-43d9g.com/signin").unwrap();
+        annotate_record(&mut record, Some(&list));
+
+        assert_eq!(
+            record.get_field("unicode_host"),
+            Some("\u{440}\u{430}ypal.com")
+        );
+        assert_eq!(record.get_field("mixed_script"), Some("true"));
+        assert_eq!(record.get_field("homograph_of"), Some("paypal.com"));
+
+        let mut record = to_record("https://www.example.com/").unwrap();
+        annotate_record(&mut record, Some(&list));
+        assert_eq!(record.get_field("mixed_script"), Some("false"));
+        assert_eq!(record.get_field("homograph_of"), None);
+    }
+}
@@ -0,0 +1,289 @@
+use clap::ValueEnum;
+use std::fmt;
+
+use crate::access_log::{parse_clf_line, parse_nginx_json_line, LogDefaults};
+use crate::formatter::{UrlRecord, RECORD_FIELDS};
+use crate::records::parse_records;
+use crate::robots::parse_robots;
+use crate::sitemap::parse_sitemap;
+use crate::structured::{parse_components, parse_csv, parse_jsonl_line};
+use crate::traffic::{parse_burp, parse_har};
+use crate::warc::parse_warc;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum InputFormat {
+    #[default]
+    Lines,
+    Clf,
+    Combined,
+    NginxJson,
+    Har,
+    Burp,
+    Csv,
+    Jsonl,
+    Sitemap,
+    Robots,
+    Warc,
+    Records,
+    Assemble,
+}
+
+/// Pseudo-fields describing where a record came from rather than the URL itself.
+pub const INPUT_FIELDS: &[&str] = &["line", "source", "input"];
+
+/// A URL read from the input, along with any extra fields the reader found.
+/// `line` is the 1-based line the record starts on and `raw` the untouched
+/// text it was read from, when the reader knows them. `record` holds an
+/// already-parsed record when reading rexturl's own output.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InputRecord {
+    pub url: String,
+    pub fields: Vec<(String, String)>,
+    pub line: Option<usize>,
+    pub raw: Option<String>,
+    pub record: Option<UrlRecord>,
+}
+
+impl InputRecord {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn at_line(mut self, line: usize, raw: Option<&str>) -> Self {
+        self.line = Some(line);
+        self.raw = raw.map(str::to_string);
+        self
+    }
+
+    pub fn push_field(&mut self, name: &str, value: impl Into<String>) {
+        self.fields.push((name.to_string(), value.into()));
+    }
+
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Renames fields named like a URL component or one of [`INPUT_FIELDS`],
+    /// which would hide them in the output, to `input.<name>`. Returns the
+    /// original names of the renamed fields.
+    pub fn rename_reserved_fields(&mut self) -> Vec<String> {
+        let mut renamed = Vec::new();
+        for (name, _) in &mut self.fields {
+            if RECORD_FIELDS.contains(&name.as_str()) || INPUT_FIELDS.contains(&name.as_str()) {
+                let new_name = format!("input.{name}");
+                renamed.push(std::mem::replace(name, new_name));
+            }
+        }
+        renamed
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for InputError {}
+
+#[derive(Debug, Clone, Default)]
+pub struct InputOptions {
+    pub log: LogDefaults,
+    pub url_column: String,
+    pub url_field: String,
+    pub base: Option<String>,
+    pub component_columns: Vec<(String, String)>,
+    pub null_value: String,
+    /// Delimiter and quote character of comma-separated `assemble` tables,
+    /// `,` and `"` when unset.
+    pub csv_delimiter: Option<u8>,
+    pub csv_quote: Option<u8>,
+}
+
+/// Splits `input` into records according to `format`. Blank lines are skipped;
+/// lines that cannot be read are returned as errors so callers can count them.
+/// Errors that make the whole input unreadable, such as a malformed HAR
+/// document, a missing CSV column or robots.txt rules without a base URL, are
+/// returned as the outer error.
+pub fn read_records(
+    format: InputFormat,
+    input: &str,
+    options: &InputOptions,
+) -> Result<Vec<Result<InputRecord, InputError>>, InputError> {
+    let records = match format {
+        InputFormat::Lines => read_lines(input, |line| Ok(InputRecord::new(line))),
+        InputFormat::Clf | InputFormat::Combined => {
+            read_lines(input, |line| parse_clf_line(line, &options.log))
+        }
+        InputFormat::NginxJson => {
+            read_lines(input, |line| parse_nginx_json_line(line, &options.log))
+        }
+        InputFormat::Jsonl => read_lines(input, |line| parse_jsonl_line(line, &options.url_field)),
+        InputFormat::Csv => parse_csv(input, &options.url_column)?,
+        InputFormat::Har => parse_har(input)?.into_iter().map(Ok).collect(),
+        InputFormat::Burp => parse_burp(input)?,
+        InputFormat::Sitemap => parse_sitemap(input)?,
+        InputFormat::Robots => parse_robots(input, options.base.as_deref())?,
+        InputFormat::Warc => parse_warc(input.as_bytes())?,
+        InputFormat::Records => parse_records(input)?,
+        InputFormat::Assemble => parse_components(
+            input,
+            &options.component_columns,
+            &options.null_value,
+            options.csv_delimiter.unwrap_or(b','),
+            options.csv_quote.unwrap_or(b'"'),
+        )?,
+    };
+    Ok(records)
+}
+
+/// Like [`read_records`], but starting from raw bytes. Binary formats such as
+/// WARC are parsed as bytes; everything else is decoded as UTF-8 first,
+/// replacing invalid sequences.
+pub fn read_records_from_bytes(
+    format: InputFormat,
+    input: &[u8],
+    options: &InputOptions,
+) -> Result<Vec<Result<InputRecord, InputError>>, InputError> {
+    match format {
+        InputFormat::Warc => parse_warc(input),
+        _ => read_records(format, &String::from_utf8_lossy(input), options),
+    }
+}
+
+fn read_lines<F>(input: &str, parse: F) -> Vec<Result<InputRecord, InputError>>
+where
+    F: Fn(&str) -> Result<InputRecord, String>,
+{
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            parse(line.trim())
+                .map(|record| record.at_line(index + 1, Some(line)))
+                .map_err(|message| InputError {
+                    line: index + 1,
+                    message,
+                })
+        })
+        .collect()
+}
+
+/// Adds a field unless the value is missing, blank or `-`, which access logs
+/// and traffic exports use for "no value".
+pub fn push_present(record: &mut InputRecord, name: &str, value: Option<&str>) {
+    if let Some(value) = value.map(str::trim).filter(|v| !v.is_empty() && *v != "-") {
+        record.push_field(name, value);
+    }
+}
+
+/// Finds the 1-based line numbers of a series of byte offsets. Readers that
+/// report many positions in order only pay for the bytes between them rather
+/// than rescanning the input from the start each time.
+#[derive(Debug, Clone)]
+pub struct LineCursor<'a> {
+    input: &'a [u8],
+    offset: usize,
+    line: usize,
+}
+
+impl<'a> LineCursor<'a> {
+    pub fn new<T: AsRef<[u8]> + ?Sized>(input: &'a T) -> Self {
+        Self {
+            input: input.as_ref(),
+            offset: 0,
+            line: 1,
+        }
+    }
+
+    /// Returns the 1-based line number of `position`. Moving forward only
+    /// counts the bytes since the previous call; moving back counts the bytes
+    /// in between.
+    pub fn line_at(&mut self, position: usize) -> usize {
+        let position = position.min(self.input.len());
+        let newlines = |range: &[u8]| range.iter().filter(|&&b| b == b'\n').count();
+        if position >= self.offset {
+            self.line += newlines(&self.input[self.offset..position]);
+        } else {
+            self.line -= newlines(&self.input[position..self.offset]);
+        }
+        self.offset = position;
+        self.line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename_reserved_fields() {
+        let mut record = InputRecord::new("https://example.com/");
+        record.push_field("path", "/from/csv");
+        record.push_field("line", "7");
+        record.push_field("owner", "ops");
+
+        assert_eq!(record.rename_reserved_fields(), vec!["path", "line"]);
+        assert_eq!(record.field("input.path"), Some("/from/csv"));
+        assert_eq!(record.field("input.line"), Some("7"));
+        assert_eq!(record.field("owner"), Some("ops"));
+        assert_eq!(record.field("path"), None);
+    }
+
+    #[test]
+    fn test_read_lines() {
+        let records = read_records(
+            InputFormat::Lines,
+            "https://a.com\n\n  https://b.com  \n",
+            &InputOptions::default(),
+        )
+        .unwrap();
+        let records: Vec<_> = records.into_iter().map(Result::unwrap).collect();
+        assert_eq!(records[0].url, "https://a.com");
+        assert_eq!(records[1].url, "https://b.com");
+        assert_eq!(records[1].line, Some(3));
+        assert_eq!(records[1].raw.as_deref(), Some("  https://b.com  "));
+    }
+
+    #[test]
+    fn test_read_access_log_reports_line_numbers() {
+        let options = InputOptions {
+            log: LogDefaults {
+                host: Some("example.com".to_string()),
+                scheme: "https".to_string(),
+            },
+            ..InputOptions::default()
+        };
+        let log = "1.2.3.4 - - [01/Jan/2024:00:00:01 +0000] \"GET / HTTP/1.1\" 200 5\n\
+                   not a log line\n";
+        let records = read_records(InputFormat::Combined, log, &options).unwrap();
+
+        assert_eq!(records[0].as_ref().unwrap().url, "https://example.com/");
+        let err = records[1].as_ref().unwrap_err();
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn test_line_cursor() {
+        let input = "a\nbb\nccc";
+        let mut cursor = LineCursor::new(input);
+        assert_eq!(cursor.line_at(0), 1);
+        assert_eq!(cursor.line_at(2), 2);
+        assert_eq!(cursor.line_at(5), 3);
+        assert_eq!(cursor.line_at(1), 1);
+        assert_eq!(cursor.line_at(100), 3);
+    }
+}
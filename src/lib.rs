@@ -1,16 +1,35 @@
+pub mod access_log;
+pub mod color;
 pub mod config;
+pub mod document;
 pub mod domain;
 pub mod error;
+pub mod explain;
+pub mod extract;
+pub mod filter;
 pub mod formatter;
+pub mod homograph;
+pub mod input;
 pub mod output;
 pub mod parser;
 pub mod processor;
+pub mod records;
+pub mod report;
+pub mod risk;
+pub mod robots;
+pub mod scope;
+pub mod sitemap;
+pub mod source;
+pub mod structured;
+pub mod traffic;
+pub mod typosquat;
 pub mod url;
 pub mod url_parser;
+pub mod warc;
 
 pub use config::{check_for_stdin, Config};
 pub use error::AppError;
 pub use output::{custom_format_url, output_json};
 pub use parser::{extract_url_components, parse_and_extract_components, parse_url, UrlComponents};
 pub use processor::{process_url, process_urls_parallel, process_urls_streaming};
-pub use url::{Url, UrlParseError};
+pub use url::{Url, UrlParseError, UrlParts};
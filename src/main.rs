@@ -1,17 +1,57 @@
 use clap::Parser;
-use std::io::{self, BufRead};
+use std::ops::Range;
 use std::process;
 
+use rexturl::access_log::LogDefaults;
+use rexturl::color::{use_color, Palette, COLORS_ENV};
+use rexturl::config::Command;
+use rexturl::document::{print_xml, print_yaml};
+use rexturl::explain::explain;
+use rexturl::extract::extract_urls;
+use rexturl::filter::Filter;
 use rexturl::formatter::{
-    print_custom, print_json, print_jsonl, print_plain, print_sql, print_tabular, to_record,
-    Format, UrlRecord,
+    json_schema, print_custom, print_json, print_jsonl, print_plain, print_sql, print_table,
+    print_tabular, template_fields, to_record, Format, SqlOptions, Tabular, UrlRecord,
 };
+use rexturl::homograph::{self, ProtectList, HOMOGRAPH_FIELDS};
+use rexturl::input::{
+    read_records_from_bytes, InputError, InputFormat, InputOptions, InputRecord, LineCursor,
+};
+use rexturl::records::complete_record;
+use rexturl::report::{print_html, print_markdown};
+use rexturl::risk::{self, RISK_FIELDS};
+use rexturl::scope::{self, Scope, SCOPE_FIELDS};
+use rexturl::source::expand_inputs;
+use rexturl::structured::parse_component_columns;
+use rexturl::typosquat::{self, WatchList, SIMILARITY_FIELDS};
 use rexturl::{check_for_stdin, AppError, Config};
 
 fn main() -> Result<(), AppError> {
     let config = Config::parse();
 
-    if config.urls.is_empty() {
+    let no_inputs = config.urls.is_empty() && config.files.is_empty() && config.input.is_empty();
+    if let Some(Command::Explain { urls }) = &config.command {
+        let palette = color_palette(&config);
+        let mut failed = false;
+        for (i, url) in urls.iter().enumerate() {
+            if i > 0 {
+                println!();
+            }
+            match explain(url, palette.as_ref()) {
+                Ok(text) => print!("{text}"),
+                Err(e) => {
+                    eprintln!("Error: Cannot parse {url}: {e}");
+                    failed = true;
+                }
+            }
+        }
+        if failed {
+            process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if no_inputs && !config.json_schema {
         check_for_stdin()?;
     }
 
@@ -22,7 +62,170 @@ fn main() -> Result<(), AppError> {
         config.format
     };
 
-    let fields: Vec<&str> = if let Some(fields_str) = &config.fields {
+    if config.csv_delimiter == config.csv_quote
+        || ['\n', '\r'].contains(&config.csv_delimiter)
+        || ['\n', '\r'].contains(&config.csv_quote)
+    {
+        eprintln!("Error: --csv-delimiter and --csv-quote must be different and not line breaks");
+        process::exit(1);
+    }
+
+    if config.input_format == InputFormat::Assemble
+        && !(config.csv_delimiter.is_ascii() && config.csv_quote.is_ascii())
+    {
+        eprintln!("Error: --csv-delimiter and --csv-quote must be ASCII to read CSV input");
+        process::exit(1);
+    }
+
+    if format == Format::Sql {
+        if let Err(e) = config.sql_dialect.quote_table_name(&config.sql_table) {
+            eprintln!("Error: Invalid --sql-table: {e}");
+            process::exit(1);
+        }
+    }
+
+    let mut contents: Vec<(Option<String>, Vec<u8>)> = Vec::new();
+    if !config.urls.is_empty() {
+        contents.push((None, config.urls.join("\n").into_bytes()));
+    }
+    let mut arguments: Vec<String> = config.files.iter().chain(&config.input).cloned().collect();
+    if arguments.is_empty() && config.urls.is_empty() && !config.json_schema {
+        arguments.push("-".to_string());
+    }
+    let sources = match expand_inputs(&arguments) {
+        Ok(sources) => sources,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+    };
+    for source in sources {
+        match source.read_bytes() {
+            Ok(bytes) => contents.push((Some(source.name), bytes)),
+            Err(e) => {
+                eprintln!("Error: Failed to read {}: {e}", source.name);
+                process::exit(1);
+            }
+        }
+    }
+
+    let component_columns = match config
+        .component_columns
+        .as_deref()
+        .map(parse_component_columns)
+    {
+        Some(Ok(columns)) => columns,
+        Some(Err(e)) => {
+            eprintln!("Error: Invalid --component-columns: {e}");
+            process::exit(1);
+        }
+        None => Vec::new(),
+    };
+    let options = InputOptions {
+        log: LogDefaults {
+            host: config.log_host.clone(),
+            scheme: config.log_scheme.clone(),
+        },
+        url_column: config.url_column.clone(),
+        url_field: config.url_field.clone(),
+        base: config.base.clone(),
+        component_columns,
+        null_value: config.null_empty.clone(),
+        csv_delimiter: u8::try_from(config.csv_delimiter).ok(),
+        csv_quote: u8::try_from(config.csv_quote).ok(),
+    };
+    let mut inputs: Vec<(Option<&str>, Result<InputRecord, InputError>)> = Vec::new();
+    for (source, bytes) in &contents {
+        let records = match config.extract {
+            Some(mode) => {
+                let text = String::from_utf8_lossy(bytes);
+                let mut lines = LineCursor::new(text.as_ref());
+                let mut current = 0..0;
+                extract_urls(mode, &text, config.base.as_deref())
+                    .into_iter()
+                    .map(|found| {
+                        let line = lines.line_at(found.offset);
+                        if !current.contains(&found.offset) {
+                            current = line_span(&text, found.offset);
+                        }
+                        let raw = text[current.clone()].trim_end_matches('\r');
+                        let mut record = InputRecord::new(found.url).at_line(line, Some(raw));
+                        record.push_field("offset", found.offset.to_string());
+                        Ok(record)
+                    })
+                    .collect()
+            }
+            None => match read_records_from_bytes(config.input_format, bytes, &options) {
+                Ok(records) => records,
+                Err(e) => {
+                    eprintln!("Error: Failed to read input {}{e}", source_prefix(source));
+                    process::exit(1);
+                }
+            },
+        };
+        inputs.extend(
+            records
+                .into_iter()
+                .map(|record| (source.as_deref(), record)),
+        );
+    }
+
+    let mut renamed: Vec<String> = Vec::new();
+    for input in inputs
+        .iter_mut()
+        .filter_map(|(_, input)| input.as_mut().ok())
+    {
+        for name in input.rename_reserved_fields() {
+            if !renamed.contains(&name) {
+                eprintln!(
+                    "Warning: Input column {name:?} clashes with a built-in field, renamed to \"input.{name}\""
+                );
+                renamed.push(name);
+            }
+        }
+    }
+
+    let mut input_columns: Vec<String> = Vec::new();
+    if matches!(
+        config.input_format,
+        InputFormat::Csv | InputFormat::Jsonl | InputFormat::Assemble
+    ) {
+        let records = inputs.iter().filter_map(|(_, input)| input.as_ref().ok());
+        for (name, _) in records.flat_map(|input| &input.fields) {
+            if !input_columns.contains(name) {
+                input_columns.push(name.clone());
+            }
+        }
+    }
+    if config.input_format == InputFormat::Records {
+        let records = inputs
+            .iter()
+            .filter_map(|(_, input)| input.as_ref().ok()?.record.as_ref());
+        for record in records {
+            let components = [
+                "scheme",
+                "username",
+                "subdomain",
+                "hostname",
+                "port",
+                "path",
+                "query",
+                "fragment",
+                "domain",
+            ];
+            let components = components
+                .into_iter()
+                .filter(|name| record.get_field(name).is_some())
+                .map(str::to_string);
+            for name in components.chain(record.extra.keys().cloned()) {
+                if !input_columns.contains(&name) {
+                    input_columns.push(name);
+                }
+            }
+        }
+    }
+
+    let mut fields: Vec<&str> = if let Some(fields_str) = &config.fields {
         fields_str.split(',').map(|s| s.trim()).collect()
     } else if config.all {
         eprintln!("Warning: --all is deprecated, use --fields with specific field names");
@@ -70,24 +273,151 @@ fn main() -> Result<(), AppError> {
         auto_fields
     };
 
-    let input_urls: Vec<String> = if !config.urls.is_empty() {
-        config.urls
-    } else {
-        let stdin = io::stdin();
-        stdin.lock().lines().filter_map(|line| line.ok()).collect()
+    if config.fields.is_none() {
+        fields.extend(input_columns.iter().map(String::as_str));
+    }
+    if config.risk && config.fields.is_none() {
+        fields.extend(RISK_FIELDS);
+    }
+    if config.protect_list.is_some() && config.fields.is_none() {
+        fields.extend(["mixed_script", "homograph_of"]);
+    }
+    if config.similar_to.is_some() && config.fields.is_none() {
+        fields.extend(SIMILARITY_FIELDS);
+    }
+    if config.scope_annotate && config.fields.is_none() {
+        fields.extend(SCOPE_FIELDS);
+    }
+
+    if config.json_schema {
+        let schema = json_schema(&fields, config.typed);
+        println!("{}", serde_json::to_string_pretty(&schema)?);
+        return Ok(());
+    }
+
+    let mut referenced_fields: Vec<String> = fields.iter().map(|f| f.to_string()).collect();
+    if format == Format::Custom {
+        let template = config.template.as_deref().unwrap_or("{url}");
+        if let Ok(template_fields) = template_fields(template) {
+            referenced_fields.extend(template_fields);
+        }
+    }
+
+    let filter = match &config.filter {
+        Some(expression) => match Filter::parse(expression) {
+            Ok(filter) => {
+                referenced_fields.extend(filter.fields().iter().cloned());
+                Some(filter)
+            }
+            Err(e) => {
+                eprintln!("Error: Invalid filter expression: {e}");
+                eprintln!("  {expression}");
+                eprintln!("  {}^", " ".repeat(e.position));
+                process::exit(1);
+            }
+        },
+        None => None,
+    };
+
+    let wants_field = |names: &[&str]| {
+        referenced_fields
+            .iter()
+            .any(|field| names.contains(&field.as_str()))
+    };
+    let score_risk = config.risk || wants_field(RISK_FIELDS);
+    let check_homographs = config.protect_list.is_some() || wants_field(HOMOGRAPH_FIELDS);
+
+    let protect_list = match &config.protect_list {
+        Some(path) => match ProtectList::from_file(path) {
+            Ok(list) => Some(list),
+            Err(e) => {
+                eprintln!("Error: Failed to read protect list {}: {e}", path.display());
+                process::exit(1);
+            }
+        },
+        None => None,
+    };
+
+    let watch_list = match &config.similar_to {
+        Some(path) => match WatchList::from_file(path) {
+            Ok(list) => Some(list),
+            Err(e) => {
+                eprintln!("Error: Failed to read watch list {}: {e}", path.display());
+                process::exit(1);
+            }
+        },
+        None => None,
+    };
+
+    let scope = match &config.scope {
+        Some(path) => match Scope::from_file(path) {
+            Ok(scope) => Some(scope),
+            Err(e) => {
+                eprintln!("Error: Failed to load scope file {}: {e}", path.display());
+                process::exit(1);
+            }
+        },
+        None => None,
     };
 
     let mut records: Vec<UrlRecord> = Vec::new();
     let mut parse_errors = 0;
 
-    for url_str in input_urls {
-        let url_str = url_str.trim();
-        if url_str.is_empty() {
-            continue;
-        }
+    for (source, input) in inputs {
+        let mut input = match input {
+            Ok(input) => input,
+            Err(e) => {
+                parse_errors += 1;
+                if config.strict {
+                    eprintln!("Error: Failed to read input {}{e}", source_prefix(&source));
+                }
+                continue;
+            }
+        };
+        let url_str = input.url.as_str();
+        let parsed = match input.record.take() {
+            Some(mut record) => {
+                complete_record(&mut record);
+                Ok(record)
+            }
+            None => to_record(url_str),
+        };
 
-        match to_record(url_str) {
-            Ok(record) => records.push(record),
+        match parsed {
+            Ok(mut record) => {
+                for (name, value) in input.fields {
+                    record.set_extra(&name, value);
+                }
+                if let Some(line) = input.line {
+                    record.set_extra("line", line.to_string());
+                }
+                if let Some(source) = source {
+                    record.set_extra("source", source);
+                }
+                record.set_extra("input", input.raw.unwrap_or(input.url));
+                if score_risk {
+                    risk::annotate_record(&mut record);
+                }
+                if check_homographs {
+                    homograph::annotate_record(&mut record, protect_list.as_ref());
+                }
+                if let Some(watch_list) = &watch_list {
+                    typosquat::annotate_record(&mut record, watch_list);
+                }
+                if let Some(scope) = &scope {
+                    if config.scope_annotate {
+                        scope::annotate_record(&mut record, scope);
+                    } else if !scope.contains_record(&record) {
+                        continue;
+                    }
+                }
+                if let Some(filter) = &filter {
+                    if !filter.matches(&record) {
+                        continue;
+                    }
+                }
+                records.push(record)
+            }
             Err(_) => {
                 parse_errors += 1;
                 if config.strict {
@@ -118,13 +448,22 @@ fn main() -> Result<(), AppError> {
         });
     }
 
+    let palette = color_palette(&config);
+
     match format {
-        Format::Plain => print_plain(&records, &fields, &config.null_empty, config.no_newline),
+        Format::Plain => print_plain(
+            &records,
+            &fields,
+            &config.null_empty,
+            palette.as_ref(),
+            config.no_newline,
+        ),
         Format::Tsv => print_tabular(
             &records,
             &fields,
             config.header,
-            '\t',
+            Tabular::Tsv,
+            config.bom,
             &config.null_empty,
             config.no_newline,
         ),
@@ -132,18 +471,54 @@ fn main() -> Result<(), AppError> {
             &records,
             &fields,
             config.header,
-            ',',
+            Tabular::Csv {
+                delimiter: config.csv_delimiter,
+                quote: config.csv_quote,
+            },
+            config.bom,
+            &config.null_empty,
+            config.no_newline,
+        ),
+        Format::Table => print_table(
+            &records,
+            &fields,
+            config.table_style,
+            config.max_width,
+            &config.null_empty,
+            config.no_newline,
+        ),
+        Format::Markdown => {
+            print_markdown(&records, &fields, &config.null_empty, config.no_newline)
+        }
+        Format::Html => print_html(
+            &records,
+            &fields,
+            config.group_by_domain,
             &config.null_empty,
             config.no_newline,
         ),
+        Format::Xml => print_xml(&records, &fields, config.pretty, config.no_newline),
+        Format::Yaml => print_yaml(
+            &records,
+            &fields,
+            config.typed,
+            config.pretty,
+            config.no_newline,
+        ),
         Format::Json => {
-            if let Err(e) = print_json(&records, &fields, config.pretty, config.no_newline) {
+            if let Err(e) = print_json(
+                &records,
+                &fields,
+                config.typed,
+                config.pretty,
+                config.no_newline,
+            ) {
                 eprintln!("Error: Failed to serialize JSON: {e}");
                 process::exit(1);
             }
         }
         Format::Jsonl => {
-            if let Err(e) = print_jsonl(&records, &fields, config.no_newline) {
+            if let Err(e) = print_jsonl(&records, &fields, config.typed, config.no_newline) {
                 eprintln!("Error: Failed to serialize JSONL: {e}");
                 process::exit(1);
             }
@@ -156,14 +531,14 @@ fn main() -> Result<(), AppError> {
             }
         }
         Format::Sql => {
-            if let Err(e) = print_sql(
-                &records,
-                &fields,
-                &config.sql_table,
-                config.sql_dialect,
-                config.sql_create_table,
-                config.no_newline,
-            ) {
+            let options = SqlOptions {
+                table: &config.sql_table,
+                dialect: config.sql_dialect,
+                create_table: config.sql_create_table,
+                indexes: config.sql_indexes,
+                batch_size: config.sql_batch_size,
+            };
+            if let Err(e) = print_sql(&records, &fields, options, config.no_newline) {
                 eprintln!("Error: Failed to generate SQL: {e}");
                 process::exit(1);
             }
@@ -176,3 +551,36 @@ fn main() -> Result<(), AppError> {
 
     Ok(())
 }
+
+/// The palette to color output with, or `None` when color is off. The
+/// palette starts from the defaults, then applies `REXTURL_COLORS` and
+/// `--colors` in turn.
+fn color_palette(config: &Config) -> Option<Palette> {
+    use_color(config.color).then(|| {
+        let mut palette = Palette::default();
+        let env = std::env::var(COLORS_ENV).ok();
+        for (name, spec) in [
+            (COLORS_ENV, env.as_deref()),
+            ("--colors", config.colors.as_deref()),
+        ] {
+            if let Some(Err(e)) = spec.map(|spec| palette.apply(spec)) {
+                eprintln!("Warning: Invalid {name}: {e}");
+            }
+        }
+        palette
+    })
+}
+
+/// The byte range of the line containing `offset`, without its line break.
+fn line_span(text: &str, offset: usize) -> Range<usize> {
+    let start = text[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let end = text[offset..].find('\n').map_or(text.len(), |i| offset + i);
+    start..end
+}
+
+fn source_prefix<S: AsRef<str>>(source: &Option<S>) -> String {
+    match source {
+        Some(name) if name.as_ref() != "-" => format!("{}: ", name.as_ref()),
+        _ => String::new(),
+    }
+}
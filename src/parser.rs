@@ -14,6 +14,16 @@ pub struct UrlComponents {
     pub fragment: String,
 }
 
+pub fn default_port(scheme: &str) -> Option<u16> {
+    match scheme.to_ascii_lowercase().as_str() {
+        "http" | "ws" => Some(80),
+        "https" | "wss" => Some(443),
+        "ftp" => Some(21),
+        "ssh" | "sftp" => Some(22),
+        _ => None,
+    }
+}
+
 pub fn parse_url(url_str: &str) -> Result<Url, UrlParseError> {
     Url::parse(url_str)
 }
@@ -71,6 +81,97 @@ pub fn extract_url_components(url: &Url) -> UrlComponents {
     }
 }
 
+fn has_scheme(reference: &str) -> bool {
+    match reference.find(':') {
+        Some(colon) if colon > 0 => {
+            let scheme = &reference[..colon];
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        }
+        _ => false,
+    }
+}
+
+fn remove_dot_segments(path: &str) -> String {
+    let mut output: Vec<&str> = Vec::new();
+    let segments: Vec<&str> = path.split('/').collect();
+
+    for (i, segment) in segments.iter().enumerate() {
+        let is_last = i == segments.len() - 1;
+        match *segment {
+            "." => {
+                if is_last {
+                    output.push("");
+                }
+            }
+            ".." => {
+                if output.len() > 1 {
+                    output.pop();
+                }
+                if is_last {
+                    output.push("");
+                }
+            }
+            _ => output.push(segment),
+        }
+    }
+
+    let joined = output.join("/");
+    if path.starts_with('/') && !joined.starts_with('/') {
+        format!("/{joined}")
+    } else {
+        joined
+    }
+}
+
+/// Resolves `reference` against an absolute `base` URL following RFC 3986 section 5.
+pub fn resolve_url(base: &str, reference: &str) -> Option<String> {
+    let reference = reference.trim();
+    if has_scheme(reference) {
+        return Some(reference.to_string());
+    }
+
+    let scheme_end = base.find("://")?;
+    let scheme = &base[..scheme_end];
+    let after_scheme = &base[scheme_end + 3..];
+    let authority_end = after_scheme
+        .find(['/', '?', '#'])
+        .unwrap_or(after_scheme.len());
+    let authority = &after_scheme[..authority_end];
+    let rest = &after_scheme[authority_end..];
+    let base_without_fragment = rest.split('#').next().unwrap_or_default();
+    let base_path = base_without_fragment.split('?').next().unwrap_or_default();
+    let base_path = if base_path.is_empty() { "/" } else { base_path };
+
+    if let Some(network_path) = reference.strip_prefix("//") {
+        return Some(format!("{scheme}://{network_path}"));
+    }
+
+    let origin = format!("{scheme}://{authority}");
+    if reference.is_empty() {
+        return Some(format!("{origin}{base_without_fragment}"));
+    }
+    if reference.starts_with('#') {
+        return Some(format!("{origin}{base_without_fragment}{reference}"));
+    }
+    if reference.starts_with('?') {
+        return Some(format!("{origin}{base_path}{reference}"));
+    }
+
+    let suffix_start = reference.find(['?', '#']).unwrap_or(reference.len());
+    let (path, suffix) = reference.split_at(suffix_start);
+    let merged = if path.starts_with('/') {
+        path.to_string()
+    } else {
+        let directory = &base_path[..base_path.rfind('/').map_or(0, |i| i + 1)];
+        format!("{directory}{path}")
+    };
+
+    Some(format!("{origin}{}{suffix}", remove_dot_segments(&merged)))
+}
+
 pub fn parse_and_extract_components(url_str: &str) -> Result<UrlComponents, UrlParseError> {
     let url = parse_url(url_str)?;
     Ok(extract_url_components(&url))
@@ -154,6 +255,51 @@ mod tests {
         assert_eq!(components.fragment, "#results");
     }
 
+    #[test]
+    fn test_default_port() {
+        assert_eq!(default_port("https"), Some(443));
+        assert_eq!(default_port("HTTP"), Some(80));
+        assert_eq!(default_port("gopher"), None);
+    }
+
+    #[test]
+    fn test_resolve_url() {
+        let base = "http://a.example/b/c/d;p?q#f";
+        assert_eq!(resolve_url(base, "g").unwrap(), "http://a.example/b/c/g");
+        assert_eq!(resolve_url(base, "./g").unwrap(), "http://a.example/b/c/g");
+        assert_eq!(resolve_url(base, "g/").unwrap(), "http://a.example/b/c/g/");
+        assert_eq!(resolve_url(base, "/g").unwrap(), "http://a.example/g");
+        assert_eq!(resolve_url(base, "//g").unwrap(), "http://g");
+        assert_eq!(
+            resolve_url(base, "?y").unwrap(),
+            "http://a.example/b/c/d;p?y"
+        );
+        assert_eq!(
+            resolve_url(base, "g?y#s").unwrap(),
+            "http://a.example/b/c/g?y#s"
+        );
+        assert_eq!(
+            resolve_url(base, "#s").unwrap(),
+            "http://a.example/b/c/d;p?q#s"
+        );
+        assert_eq!(resolve_url(base, "").unwrap(), "http://a.example/b/c/d;p?q");
+        assert_eq!(resolve_url(base, "..").unwrap(), "http://a.example/b/");
+        assert_eq!(resolve_url(base, "../../g").unwrap(), "http://a.example/g");
+        assert_eq!(
+            resolve_url(base, "../../../g").unwrap(),
+            "http://a.example/g"
+        );
+        assert_eq!(
+            resolve_url(base, "https://other.example/x").unwrap(),
+            "https://other.example/x"
+        );
+        assert_eq!(
+            resolve_url("https://example.com", "docs").unwrap(),
+            "https://example.com/docs"
+        );
+        assert_eq!(resolve_url("not a url", "docs"), None);
+    }
+
     #[test]
     fn test_edge_cases() {
         let components = parse_and_extract_components("https://example.com").unwrap();
@@ -0,0 +1,157 @@
+use serde_json::Value;
+
+use crate::formatter::{to_record, UrlRecord};
+use crate::input::{InputError, InputRecord};
+
+/// Reads rexturl's own `--format json` or `--format jsonl` output back into
+/// records. A document whose top level is a `{"urls": [...]}` object (or a
+/// bare array) is read as JSON output; anything else is read one object per
+/// line. When `url` is missing it is rebuilt from the components; see
+/// [`complete_record`] for the reverse.
+pub fn parse_records(input: &str) -> Result<Vec<Result<InputRecord, InputError>>, InputError> {
+    let entries = match serde_json::from_str::<Value>(input) {
+        Ok(Value::Object(mut document)) if document.contains_key("urls") => {
+            match document.remove("urls") {
+                Some(Value::Array(entries)) => entries,
+                _ => {
+                    return Err(InputError {
+                        line: 1,
+                        message: "\"urls\" must be an array of records".to_string(),
+                    })
+                }
+            }
+        }
+        Ok(Value::Array(entries)) => entries,
+        _ => return Ok(parse_record_lines(input)),
+    };
+
+    let records = entries
+        .into_iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let raw = entry.to_string();
+            parse_record_value(entry)
+                .map(|record| {
+                    let mut input = into_input(record);
+                    input.raw = Some(raw);
+                    input
+                })
+                .map_err(|message| InputError {
+                    line: 1,
+                    message: format!("record {}: {message}", index + 1),
+                })
+        })
+        .collect();
+    Ok(records)
+}
+
+fn parse_record_lines(input: &str) -> Vec<Result<InputRecord, InputError>> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str::<Value>(line.trim())
+                .map_err(|e| format!("invalid JSON: {e}"))
+                .and_then(parse_record_value)
+                .map(|record| into_input(record).at_line(index + 1, Some(line)))
+                .map_err(|message| InputError {
+                    line: index + 1,
+                    message,
+                })
+        })
+        .collect()
+}
+
+/// Rebuilds one record from a JSON object written by rexturl.
+pub fn parse_record_value(value: Value) -> Result<UrlRecord, String> {
+    if !value.is_object() {
+        return Err("expected a JSON object".to_string());
+    }
+    let mut record: UrlRecord =
+        serde_json::from_value(value).map_err(|e| format!("invalid record: {e}"))?;
+
+    if record.url.is_none() {
+        record.url = record.compose_url().ok();
+    }
+    if record.url.is_none() && record.hostname.is_none() && record.path.is_none() {
+        return Err("record has no url and no components to rebuild one from".to_string());
+    }
+    Ok(record)
+}
+
+/// Fills the components a read-back record lacks by parsing its URL, so
+/// output written with a narrow `--fields` can still be filtered on the rest.
+pub fn complete_record(record: &mut UrlRecord) {
+    if let Some(parsed) = record.url.as_deref().and_then(|url| to_record(url).ok()) {
+        record.fill_missing(parsed);
+    }
+}
+
+fn into_input(record: UrlRecord) -> InputRecord {
+    let mut input = InputRecord::new(record.url.clone().unwrap_or_default());
+    input.record = Some(record);
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_jsonl_records() {
+        let jsonl = r#"{"url":"https://api.example.com:8443/v1?x=1","risk_score":"40"}
+
+{"scheme":"https","hostname":"example.com","path":"/a b","port":"8080"}
+{"path":"/only"}
+{"query":"a=1"}
+not json
+"#;
+        let records = parse_records(jsonl).unwrap();
+        assert_eq!(records.len(), 5);
+
+        let first = records[0].as_ref().unwrap();
+        let mut record = first.record.clone().unwrap();
+        assert_eq!(record.port, None);
+        complete_record(&mut record);
+        assert_eq!(record.port.as_deref(), Some("8443"));
+        assert_eq!(record.domain.as_deref(), Some("example.com"));
+        assert_eq!(record.get_field("risk_score"), Some("40"));
+        assert_eq!(first.line, Some(1));
+
+        let second = records[1].as_ref().unwrap();
+        assert_eq!(second.url, "https://example.com:8080/a%20b");
+        assert_eq!(second.line, Some(3));
+
+        let third = records[2].as_ref().unwrap().record.as_ref().unwrap();
+        assert_eq!(third.url, None);
+        assert_eq!(third.path.as_deref(), Some("/only"));
+
+        assert!(records[3].is_err());
+        assert_eq!(records[4].as_ref().unwrap_err().line, 6);
+    }
+
+    #[test]
+    fn test_parse_json_document() {
+        let json = r#"{
+  "urls": [
+    {"url": "https://example.com/", "status": 200, "tags": ["a"], "note": null},
+    {"subdomain": "www", "domain": "example.org", "scheme": "http"}
+  ]
+}"#;
+        let records = parse_records(json).unwrap();
+        let first = records[0].as_ref().unwrap().record.as_ref().unwrap();
+        assert_eq!(first.get_field("status"), Some("200"));
+        assert_eq!(first.get_field("tags"), Some(r#"["a"]"#));
+        assert_eq!(first.get_field("note"), None);
+
+        let second = records[1].as_ref().unwrap();
+        assert_eq!(second.url, "http://www.example.org/");
+        assert!(second.raw.as_deref().unwrap().contains("example.org"));
+    }
+
+    #[test]
+    fn test_parse_json_document_requires_array() {
+        assert!(parse_records(r#"{"urls": "https://example.com"}"#).is_err());
+    }
+}
@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::formatter::{numeric_columns, print_rendered, select_fields, UrlRecord};
+
+const HTML_STYLE: &str =
+    "body { font-family: system-ui, sans-serif; margin: 2rem; color: #1f2328; }
+h2 { font-size: 1.1rem; margin-top: 2rem; }
+h2 .count, .summary { color: #656d76; font-weight: normal; }
+table { border-collapse: collapse; margin-top: 0.5rem; }
+th, td { border: 1px solid #d0d7de; padding: 0.25rem 0.6rem; text-align: left; }
+th { background: #f6f8fa; cursor: pointer; user-select: none; white-space: nowrap; }
+th[aria-sort=ascending]::after { content: \" \\25B2\"; }
+th[aria-sort=descending]::after { content: \" \\25BC\"; }
+td { font-family: ui-monospace, monospace; word-break: break-all; }
+.num { text-align: right; }
+.null { color: #8c959f; }
+";
+
+const HTML_SCRIPT: &str = "document.querySelectorAll(\"table\").forEach(function (table) {
+  table.querySelectorAll(\"th\").forEach(function (th, column) {
+    th.addEventListener(\"click\", function () {
+      var ascending = th.getAttribute(\"aria-sort\") !== \"ascending\";
+      table.querySelectorAll(\"th\").forEach(function (other) {
+        other.removeAttribute(\"aria-sort\");
+      });
+      th.setAttribute(\"aria-sort\", ascending ? \"ascending\" : \"descending\");
+      var body = table.tBodies[0];
+      var rows = Array.from(body.rows);
+      rows.sort(function (a, b) {
+        var x = a.cells[column].textContent, y = b.cells[column].textContent;
+        var order = x !== \"\" && y !== \"\" && !isNaN(x) && !isNaN(y)
+          ? Number(x) - Number(y)
+          : x.localeCompare(y, undefined, { numeric: true });
+        return ascending ? order : -order;
+      });
+      rows.forEach(function (row) { body.appendChild(row); });
+    });
+  });
+});
+";
+
+/// Renders records as a GitHub-flavored Markdown table. Pipes in values are
+/// escaped and line breaks become `<br>`, so every record stays on one row;
+/// numeric columns are right-aligned.
+pub fn render_markdown(records: &[UrlRecord], fields: &[&str], null_value: &str) -> String {
+    let numeric = numeric_columns(records, fields);
+    let row = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+
+    let header = fields.iter().map(|field| markdown_escape(field)).collect();
+    let alignment = numeric
+        .iter()
+        .map(|&numeric| if numeric { "---:" } else { "---" }.to_string())
+        .collect();
+
+    let mut out = row(header);
+    out.push_str(&row(alignment));
+    for record in records {
+        let cells = select_fields(record, fields, null_value)
+            .iter()
+            .map(|value| markdown_escape(value))
+            .collect();
+        out.push_str(&row(cells));
+    }
+    out
+}
+
+pub fn print_markdown(records: &[UrlRecord], fields: &[&str], null_value: &str, no_newline: bool) {
+    print_rendered(&render_markdown(records, fields, null_value), no_newline);
+}
+
+fn markdown_escape(value: &str) -> String {
+    value
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace(['\n', '\r'], "<br>")
+}
+
+/// Renders records as a self-contained HTML page with a table whose columns
+/// sort when their header is clicked. With `group_by_domain`, records get one
+/// table per registrable domain, in the order the domains first appear.
+pub fn render_html(
+    records: &[UrlRecord],
+    fields: &[&str],
+    group_by_domain: bool,
+    null_value: &str,
+) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "<!DOCTYPE html>");
+    let _ = writeln!(out, "<html lang=\"en\">");
+    let _ = writeln!(out, "<head>");
+    let _ = writeln!(out, "<meta charset=\"utf-8\">");
+    let _ = writeln!(out, "<title>rexturl report</title>");
+    let _ = writeln!(out, "<style>\n{HTML_STYLE}</style>");
+    let _ = writeln!(out, "</head>");
+    let _ = writeln!(out, "<body>");
+    let _ = writeln!(out, "<h1>rexturl report</h1>");
+    let _ = writeln!(
+        out,
+        "<p class=\"summary\">{} {}</p>",
+        records.len(),
+        if records.len() == 1 { "URL" } else { "URLs" }
+    );
+
+    if group_by_domain {
+        let mut groups: Vec<(Option<&str>, Vec<&UrlRecord>)> = Vec::new();
+        let mut group_index: HashMap<Option<&str>, usize> = HashMap::new();
+        for record in records {
+            let domain = record.get_field("domain");
+            let index = *group_index.entry(domain).or_insert_with(|| {
+                groups.push((domain, Vec::new()));
+                groups.len() - 1
+            });
+            groups[index].1.push(record);
+        }
+        for (domain, group) in groups {
+            let heading = match domain {
+                Some(domain) => html_escape(domain),
+                None => format!("<span class=\"null\">{}</span>", html_escape(null_value)),
+            };
+            let _ = writeln!(out, "<section>");
+            let _ = writeln!(
+                out,
+                "<h2>{heading} <span class=\"count\">({})</span></h2>",
+                group.len()
+            );
+            html_table(&mut out, &group, fields, null_value);
+            let _ = writeln!(out, "</section>");
+        }
+    } else {
+        let records: Vec<&UrlRecord> = records.iter().collect();
+        html_table(&mut out, &records, fields, null_value);
+    }
+
+    let _ = writeln!(out, "<script>\n{HTML_SCRIPT}</script>");
+    let _ = writeln!(out, "</body>");
+    let _ = writeln!(out, "</html>");
+    out
+}
+
+pub fn print_html(
+    records: &[UrlRecord],
+    fields: &[&str],
+    group_by_domain: bool,
+    null_value: &str,
+    no_newline: bool,
+) {
+    print_rendered(
+        &render_html(records, fields, group_by_domain, null_value),
+        no_newline,
+    );
+}
+
+fn html_table(out: &mut String, records: &[&UrlRecord], fields: &[&str], null_value: &str) {
+    let numeric = numeric_columns(records, fields);
+    let class = |column: usize| {
+        if numeric[column] {
+            " class=\"num\""
+        } else {
+            ""
+        }
+    };
+
+    let _ = writeln!(out, "<table>");
+    let _ = write!(out, "<thead><tr>");
+    for (column, field) in fields.iter().enumerate() {
+        let _ = write!(out, "<th{}>{}</th>", class(column), html_escape(field));
+    }
+    let _ = writeln!(out, "</tr></thead>");
+    let _ = writeln!(out, "<tbody>");
+    for record in records {
+        let _ = write!(out, "<tr>");
+        for (column, field) in fields.iter().enumerate() {
+            let cell = match record.get_field(field) {
+                Some(value) if *field == "url" && is_web_url(value) => {
+                    let value = html_escape(value);
+                    format!("<a href=\"{value}\">{value}</a>")
+                }
+                Some(value) => html_escape(value),
+                None => {
+                    let _ = write!(out, "<td class=\"null\">{}</td>", html_escape(null_value));
+                    continue;
+                }
+            };
+            let _ = write!(out, "<td{}>{cell}</td>", class(column));
+        }
+        let _ = writeln!(out, "</tr>");
+    }
+    let _ = writeln!(out, "</tbody>");
+    let _ = writeln!(out, "</table>");
+}
+
+/// Only http and https URLs are linked, so a report of untrusted input
+/// cannot carry `javascript:` links.
+fn is_web_url(value: &str) -> bool {
+    let lower = value.to_ascii_lowercase();
+    lower.starts_with("http://") || lower.starts_with("https://")
+}
+
+pub fn html_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formatter::to_record;
+
+    #[test]
+    fn test_render_markdown() {
+        let mut record = to_record("https://example.com:8080/a?x=1|2").unwrap();
+        record.set_extra("note", "line one\nline two");
+        let other = to_record("http://example.org/").unwrap();
+
+        assert_eq!(
+            render_markdown(&[record, other], &["query", "port", "note"], "-"),
+            "| query | port | note |\n\
+             | --- | ---: | --- |\n\
+             | ?x=1\\|2 | 8080 | line one<br>line two |\n\
+             | - | - | - |\n"
+        );
+    }
+
+    #[test]
+    fn test_render_html() {
+        let records = [
+            to_record("https://www.example.com/a?b=<1>").unwrap(),
+            to_record("javascript://example.org/").unwrap(),
+            to_record("https://api.example.com:8443/").unwrap(),
+        ];
+        let html = render_html(&records, &["url", "port"], false, "-");
+        assert!(html.starts_with("<!DOCTYPE html>\n"));
+        assert!(html.contains("<p class=\"summary\">3 URLs</p>"));
+        assert!(html.contains("<th>url</th><th class=\"num\">port</th>"));
+        assert!(html.contains(
+            "<td><a href=\"https://www.example.com/a?b=&lt;1&gt;\">\
+             https://www.example.com/a?b=&lt;1&gt;</a></td>"
+        ));
+        assert!(html.contains("<td>javascript://example.org/</td><td class=\"null\">-</td>"));
+        assert!(html.contains("<td class=\"num\">8443</td>"));
+        assert_eq!(html.matches("<table>").count(), 1);
+
+        let grouped = render_html(&records, &["url"], true, "-");
+        assert_eq!(grouped.matches("<table>").count(), 2);
+        assert!(grouped.contains("<h2>example.com <span class=\"count\">(2)</span></h2>"));
+        assert!(
+            grouped.find("example.com <span").unwrap() < grouped.find("example.org <span").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_html_escape() {
+        assert_eq!(
+            html_escape("<a href=\"x\">&'"),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&#39;"
+        );
+    }
+}
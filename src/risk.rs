@@ -0,0 +1,266 @@
+use std::fmt;
+
+use crate::domain::{is_ip_literal, is_mixed_script, is_punycode, is_url_shortener};
+use crate::formatter::UrlRecord;
+use crate::parser::{parse_and_extract_components, UrlComponents};
+
+pub const RISK_FIELDS: &[&str] = &["risk_score", "risk_reasons"];
+
+pub const WATCHED_BRANDS: &[&str] = &[
+    "paypal",
+    "apple",
+    "icloud",
+    "microsoft",
+    "office365",
+    "outlook",
+    "google",
+    "amazon",
+    "facebook",
+    "instagram",
+    "netflix",
+    "linkedin",
+    "dropbox",
+    "docusign",
+    "adobe",
+    "coinbase",
+    "binance",
+    "chase",
+    "wellsfargo",
+    "bankofamerica",
+];
+
+pub const MAX_SUBDOMAIN_DEPTH: usize = 3;
+pub const MAX_HOST_LENGTH: usize = 50;
+pub const MAX_RISK_SCORE: u32 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RiskReason {
+    IpHost,
+    Userinfo,
+    Punycode,
+    MixedScript,
+    DeepSubdomain,
+    LongHost,
+    NonStandardPort,
+    Shortener,
+    BrandInSubdomain,
+}
+
+impl RiskReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RiskReason::IpHost => "ip_host",
+            RiskReason::Userinfo => "userinfo",
+            RiskReason::Punycode => "punycode",
+            RiskReason::MixedScript => "mixed_script",
+            RiskReason::DeepSubdomain => "deep_subdomain",
+            RiskReason::LongHost => "long_host",
+            RiskReason::NonStandardPort => "nonstandard_port",
+            RiskReason::Shortener => "shortener",
+            RiskReason::BrandInSubdomain => "brand_in_subdomain",
+        }
+    }
+
+    pub fn weight(&self) -> u32 {
+        match self {
+            RiskReason::IpHost => 30,
+            RiskReason::Userinfo => 25,
+            RiskReason::Punycode => 20,
+            RiskReason::MixedScript => 30,
+            RiskReason::DeepSubdomain => 15,
+            RiskReason::LongHost => 10,
+            RiskReason::NonStandardPort => 10,
+            RiskReason::Shortener => 15,
+            RiskReason::BrandInSubdomain => 25,
+        }
+    }
+}
+
+impl fmt::Display for RiskReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RiskAssessment {
+    pub score: u32,
+    pub reasons: Vec<RiskReason>,
+}
+
+impl RiskAssessment {
+    fn flag(&mut self, reason: RiskReason) {
+        self.reasons.push(reason);
+        self.score = (self.score + reason.weight()).min(MAX_RISK_SCORE);
+    }
+
+    pub fn reasons_string(&self) -> String {
+        self.reasons
+            .iter()
+            .map(RiskReason::as_str)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+pub fn assess(components: &UrlComponents) -> RiskAssessment {
+    let mut assessment = RiskAssessment::default();
+    let host = components.hostname.as_str();
+    let ip_host = is_ip_literal(host);
+
+    if ip_host {
+        assessment.flag(RiskReason::IpHost);
+    }
+    if !components.username.is_empty() {
+        assessment.flag(RiskReason::Userinfo);
+    }
+    if is_punycode(host) {
+        assessment.flag(RiskReason::Punycode);
+    }
+    if is_mixed_script(host) {
+        assessment.flag(RiskReason::MixedScript);
+    }
+    if !ip_host
+        && !components.subdomain.is_empty()
+        && components.subdomain.split('.').count() > MAX_SUBDOMAIN_DEPTH
+    {
+        assessment.flag(RiskReason::DeepSubdomain);
+    }
+    if host.len() > MAX_HOST_LENGTH {
+        assessment.flag(RiskReason::LongHost);
+    }
+    if is_nonstandard_web_port(&components.scheme, &components.port) {
+        assessment.flag(RiskReason::NonStandardPort);
+    }
+    if is_url_shortener(&components.domain) {
+        assessment.flag(RiskReason::Shortener);
+    }
+    if !ip_host && has_brand_in_subdomain(&components.subdomain, &components.domain) {
+        assessment.flag(RiskReason::BrandInSubdomain);
+    }
+
+    assessment
+}
+
+pub fn annotate_record(record: &mut UrlRecord) {
+    let Some(url) = record.url.as_deref() else {
+        return;
+    };
+    let Ok(components) = parse_and_extract_components(url) else {
+        return;
+    };
+
+    let assessment = assess(&components);
+    record.set_extra("risk_score", assessment.score.to_string());
+    if !assessment.reasons.is_empty() {
+        record.set_extra("risk_reasons", assessment.reasons_string());
+    }
+}
+
+fn is_nonstandard_web_port(scheme: &str, port: &str) -> bool {
+    let is_web = scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("https");
+    is_web && !port.is_empty() && port != "80" && port != "443"
+}
+
+/// Brands are matched against whole dot- or hyphen-separated tokens, so
+/// `paypal-login.example.com` counts but `purchase.example.com` does not.
+fn has_brand_in_subdomain(subdomain: &str, domain: &str) -> bool {
+    if subdomain.is_empty() {
+        return false;
+    }
+
+    let subdomain = subdomain.to_ascii_lowercase();
+    let registrable_label = domain
+        .split('.')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    subdomain
+        .split(['.', '-'])
+        .any(|token| WATCHED_BRANDS.contains(&token) && registrable_label != token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assess_url(url: &str) -> RiskAssessment {
+        assess(&parse_and_extract_components(url).unwrap())
+    }
+
+    #[test]
+    fn test_clean_url_has_no_risk() {
+        let assessment = assess_url("https://www.example.com/login");
+        assert_eq!(assessment.score, 0);
+        assert!(assessment.reasons.is_empty());
+    }
+
+    #[test]
+    fn test_ip_host_and_userinfo() {
+        let assessment = assess_url("http://paypal.com@192.168.1.10/login");
+        assert!(assessment.reasons.contains(&RiskReason::IpHost));
+        assert!(assessment.reasons.contains(&RiskReason::Userinfo));
+        assert_eq!(assessment.score, 55);
+    }
+
+    #[test]
+    fn test_brand_in_subdomain() {
+        let assessment = assess_url("https://paypal.com.secure-login.io/");
+        assert_eq!(assessment.reasons, vec![RiskReason::BrandInSubdomain]);
+
+        let assessment = assess_url("https://secure-paypal.account.example.net/");
+        assert_eq!(assessment.reasons, vec![RiskReason::BrandInSubdomain]);
+
+        let assessment = assess_url("https://www.paypal.com/");
+        assert!(assessment.reasons.is_empty());
+
+        for url in [
+            "https://purchase.example.com/",
+            "https://pineapple.shop.net/",
+        ] {
+            assert!(assess_url(url).reasons.is_empty(), "{url}");
+        }
+    }
+
+    #[test]
+    fn test_host_shape_rules() {
+        let assessment = assess_url("https://a.b.c.d.example.com:8443/");
+        assert_eq!(
+            assessment.reasons,
+            vec![RiskReason::DeepSubdomain, RiskReason::NonStandardPort]
+        );
+
+        let assessment = assess_url("https://xn--mnchen-3ya.de/");
+        assert_eq!(assessment.reasons, vec![RiskReason::Punycode]);
+
+        let assessment = assess_url("https://xn--ypal-43d9g.com/");
+        assert_eq!(
+            assessment.reasons,
+            vec![RiskReason::Punycode, RiskReason::MixedScript]
+        );
+
+        let assessment = assess_url("https://bit.ly/abc123");
+        assert_eq!(assessment.reasons, vec![RiskReason::Shortener]);
+    }
+
+    #[test]
+    fn test_score_is_capped() {
+        let mut assessment = RiskAssessment::default();
+        for _ in 0..10 {
+            assessment.flag(RiskReason::IpHost);
+        }
+        assert_eq!(assessment.score, MAX_RISK_SCORE);
+    }
+
+    #[test]
+    fn test_annotate_record() {
+        let mut record = crate::formatter::to_record("http://10.0.0.1:8080/").unwrap();
+        annotate_record(&mut record);
+        assert_eq!(record.get_field("risk_score"), Some("40"));
+        assert_eq!(
+            record.get_field("risk_reasons"),
+            Some("ip_host,nonstandard_port")
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use crate::input::{InputError, InputRecord};
+use crate::parser::resolve_url;
+
+/// Reads the `Allow`, `Disallow` and `Sitemap` lines of a robots.txt file.
+/// Rule paths are resolved against `base`, the site origin (or the robots.txt
+/// URL itself); `Sitemap` lines are already absolute. Each rule records the
+/// comma-separated `User-agent` group it belongs to. Empty `Disallow:` lines,
+/// which allow everything, and `*`/`$` wildcards are kept as written.
+pub fn parse_robots(
+    input: &str,
+    base: Option<&str>,
+) -> Result<Vec<Result<InputRecord, InputError>>, InputError> {
+    let mut records = Vec::new();
+    let mut agents: Vec<String> = Vec::new();
+    let mut in_agent_lines = false;
+
+    for (index, raw) in input.lines().enumerate() {
+        let line = index + 1;
+        let content = raw.split('#').next().unwrap_or_default().trim();
+        let Some((key, value)) = content.split_once(':') else {
+            continue;
+        };
+        let key = key.trim().to_ascii_lowercase();
+        let value = value.trim();
+
+        match key.as_str() {
+            "user-agent" => {
+                if !in_agent_lines {
+                    agents.clear();
+                }
+                agents.push(value.to_string());
+                in_agent_lines = true;
+            }
+            "allow" | "disallow" => {
+                in_agent_lines = false;
+                if value.is_empty() {
+                    continue;
+                }
+                let Some(base) = base else {
+                    return Err(InputError {
+                        line,
+                        message: format!(
+                            "{key} paths need --base to resolve against (e.g. https://example.com/)"
+                        ),
+                    });
+                };
+                let record = match resolve_url(base, value) {
+                    Some(url) => {
+                        let mut record = InputRecord::new(url).at_line(line, Some(raw));
+                        record.push_field("directive", key.as_str());
+                        if !agents.is_empty() {
+                            record.push_field("user_agent", agents.join(","));
+                        }
+                        Ok(record)
+                    }
+                    None => Err(InputError {
+                        line,
+                        message: format!("cannot resolve {value:?} against {base}"),
+                    }),
+                };
+                records.push(record);
+            }
+            "sitemap" => {
+                in_agent_lines = false;
+                if value.is_empty() {
+                    continue;
+                }
+                let url = match base {
+                    Some(base) => resolve_url(base, value).unwrap_or_else(|| value.to_string()),
+                    None => value.to_string(),
+                };
+                let mut record = InputRecord::new(url).at_line(line, Some(raw));
+                record.push_field("directive", "sitemap");
+                records.push(Ok(record));
+            }
+            _ => in_agent_lines = false,
+        }
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROBOTS: &str = "# robots for example.com
+User-agent: Googlebot
+User-agent: Bingbot
+Disallow: /private/   # staff only
+Allow: /private/press/
+
+User-agent: *
+Disallow:
+Disallow: /*.pdf$
+
+Sitemap: https://example.com/sitemap.xml
+";
+
+    #[test]
+    fn test_parse_robots() {
+        let records = parse_robots(ROBOTS, Some("https://example.com/robots.txt")).unwrap();
+        let records: Vec<_> = records.into_iter().map(Result::unwrap).collect();
+        assert_eq!(records.len(), 4);
+
+        assert_eq!(records[0].url, "https://example.com/private/");
+        assert_eq!(records[0].field("directive"), Some("disallow"));
+        assert_eq!(records[0].field("user_agent"), Some("Googlebot,Bingbot"));
+        assert_eq!(records[0].line, Some(4));
+
+        assert_eq!(records[1].url, "https://example.com/private/press/");
+        assert_eq!(records[1].field("directive"), Some("allow"));
+
+        assert_eq!(records[2].url, "https://example.com/*.pdf$");
+        assert_eq!(records[2].field("user_agent"), Some("*"));
+
+        assert_eq!(records[3].url, "https://example.com/sitemap.xml");
+        assert_eq!(records[3].field("directive"), Some("sitemap"));
+        assert_eq!(records[3].field("user_agent"), None);
+    }
+
+    #[test]
+    fn test_parse_robots_requires_base_for_rules() {
+        let err = parse_robots(ROBOTS, None).unwrap_err();
+        assert_eq!(err.line, 4);
+
+        let sitemaps = parse_robots("Sitemap: https://example.com/s.xml\n", None).unwrap();
+        assert_eq!(
+            sitemaps[0].as_ref().unwrap().url,
+            "https://example.com/s.xml"
+        );
+    }
+}
@@ -0,0 +1,374 @@
+use std::fmt;
+use std::fs;
+use std::net::IpAddr;
+use std::path::Path;
+
+use crate::error::AppError;
+use crate::formatter::UrlRecord;
+use crate::parser::default_port;
+
+pub const SCOPE_FIELDS: &[&str] = &["in_scope"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScopeParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ScopeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ScopeParseError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum HostPattern {
+    Any,
+    Exact(String),
+    Wildcard(String),
+    Cidr(IpAddr, u8),
+}
+
+type PortRange = (u16, u16);
+
+#[derive(Debug, Clone, PartialEq)]
+struct ScopeRule {
+    host: HostPattern,
+    ports: Option<PortRange>,
+    path_prefix: Option<String>,
+}
+
+/// In-scope and out-of-scope rules from an engagement scope file.
+///
+/// Each non-empty line is one rule; `#` starts a comment and a leading `!`
+/// marks the rule as out of scope. A rule is a host pattern (`example.com`,
+/// `*.example.com`, `*`, an IP address or a CIDR range), optionally followed by
+/// `:port` or `:low-high` and a path prefix such as `/api/`.
+#[derive(Debug, Clone, Default)]
+pub struct Scope {
+    include: Vec<ScopeRule>,
+    exclude: Vec<ScopeRule>,
+}
+
+impl Scope {
+    pub fn parse(contents: &str) -> Result<Self, ScopeParseError> {
+        let mut scope = Scope::default();
+
+        for (index, raw) in contents.lines().enumerate() {
+            let line = raw.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (excluded, entry) = match line.strip_prefix('!') {
+                Some(rest) => (true, rest.trim()),
+                None => (false, line),
+            };
+            let rule = parse_rule(entry).map_err(|message| ScopeParseError {
+                line: index + 1,
+                message,
+            })?;
+
+            if excluded {
+                scope.exclude.push(rule);
+            } else {
+                scope.include.push(rule);
+            }
+        }
+
+        Ok(scope)
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, AppError> {
+        let contents = fs::read_to_string(path)?;
+        Ok(Self::parse(&contents)?)
+    }
+
+    /// A URL is in scope when it matches an include rule and no exclude rule.
+    pub fn contains(&self, host: &str, port: Option<u16>, path: &str) -> bool {
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        self.include
+            .iter()
+            .any(|rule| rule.matches(host, port, path))
+            && !self
+                .exclude
+                .iter()
+                .any(|rule| rule.matches(host, port, path))
+    }
+
+    pub fn contains_record(&self, record: &UrlRecord) -> bool {
+        let Some(host) = record.hostname.as_deref() else {
+            return false;
+        };
+        let port = record
+            .port
+            .as_deref()
+            .and_then(|port| port.parse().ok())
+            .or_else(|| record.scheme.as_deref().and_then(default_port));
+        self.contains(host, port, record.path.as_deref().unwrap_or("/"))
+    }
+}
+
+impl ScopeRule {
+    fn matches(&self, host: &str, port: Option<u16>, path: &str) -> bool {
+        let host_matches = match &self.host {
+            HostPattern::Any => true,
+            HostPattern::Exact(expected) => host.eq_ignore_ascii_case(expected),
+            HostPattern::Wildcard(parent) => {
+                let host = host.to_ascii_lowercase();
+                host.strip_suffix(parent.as_str())
+                    .is_some_and(|prefix| prefix.ends_with('.') && prefix.len() > 1)
+            }
+            HostPattern::Cidr(network, prefix) => host
+                .parse::<IpAddr>()
+                .is_ok_and(|addr| cidr_contains(*network, *prefix, addr)),
+        };
+
+        let port_matches = match (self.ports, port) {
+            (None, _) => true,
+            (Some((low, high)), Some(port)) => (low..=high).contains(&port),
+            (Some(_), None) => false,
+        };
+
+        let path_matches = self
+            .path_prefix
+            .as_deref()
+            .is_none_or(|prefix| path.starts_with(prefix));
+
+        host_matches && port_matches && path_matches
+    }
+}
+
+fn parse_rule(entry: &str) -> Result<ScopeRule, String> {
+    let entry = entry.split_once("://").map_or(entry, |(_, rest)| rest);
+
+    if let Some(rule) = parse_cidr(entry) {
+        return rule;
+    }
+
+    let (authority, path_prefix) = match entry.find('/') {
+        Some(pos) => (&entry[..pos], Some(entry[pos..].to_string())),
+        None => (entry, None),
+    };
+
+    let (host, ports) = split_host_port(authority)?;
+    let host = host.to_ascii_lowercase();
+    let host = if host == "*" {
+        HostPattern::Any
+    } else if let Some(parent) = host.strip_prefix("*.") {
+        if parent.is_empty() || parent.contains('*') {
+            return Err(format!("invalid wildcard pattern: {host}"));
+        }
+        HostPattern::Wildcard(parent.to_string())
+    } else if host.contains('*') {
+        return Err(format!(
+            "wildcards are only supported as a leading '*.': {host}"
+        ));
+    } else if host.is_empty() {
+        return Err("missing host".to_string());
+    } else if let Ok(addr) = host.parse::<IpAddr>() {
+        let prefix = if addr.is_ipv4() { 32 } else { 128 };
+        HostPattern::Cidr(addr, prefix)
+    } else {
+        HostPattern::Exact(host)
+    };
+
+    Ok(ScopeRule {
+        host,
+        ports,
+        path_prefix,
+    })
+}
+
+fn split_host_port(authority: &str) -> Result<(&str, Option<PortRange>), String> {
+    let (host, port) = if let Some(rest) = authority.strip_prefix('[') {
+        let (host, after) = rest
+            .split_once(']')
+            .ok_or_else(|| format!("unterminated IPv6 address: {authority}"))?;
+        (host, after.strip_prefix(':'))
+    } else if authority.matches(':').count() > 1 {
+        (authority, None)
+    } else {
+        match authority.split_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        }
+    };
+
+    let ports = match port {
+        Some(spec) => Some(parse_port_range(spec)?),
+        None => None,
+    };
+    Ok((host, ports))
+}
+
+fn parse_port_range(spec: &str) -> Result<PortRange, String> {
+    let parse = |value: &str| {
+        value
+            .trim()
+            .parse::<u16>()
+            .map_err(|_| format!("invalid port: {value}"))
+    };
+
+    let (low, high) = match spec.split_once('-') {
+        Some((low, high)) => (parse(low)?, parse(high)?),
+        None => {
+            let port = parse(spec)?;
+            (port, port)
+        }
+    };
+    if low > high {
+        return Err(format!("invalid port range: {spec}"));
+    }
+    Ok((low, high))
+}
+
+/// Parses a `network/prefix` rule, which may carry a port or port range and
+/// a path prefix like a host rule does (`10.0.0.0/8:443/api/`). Returns `None`
+/// when the entry is not an IP address followed by a prefix length, so that
+/// `10.0.0.1/admin` is read as a host with a path prefix.
+fn parse_cidr(entry: &str) -> Option<Result<ScopeRule, String>> {
+    let (addr, rest) = entry.split_once('/')?;
+    let addr = addr
+        .strip_prefix('[')
+        .and_then(|addr| addr.strip_suffix(']'))
+        .unwrap_or(addr);
+    let addr: IpAddr = addr.parse().ok()?;
+    let digits = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    let (prefix, rest) = rest.split_at(digits);
+    if prefix.is_empty() || !(rest.is_empty() || rest.starts_with([':', '/'])) {
+        return None;
+    }
+
+    let max = if addr.is_ipv4() { 32 } else { 128 };
+    let prefix = match prefix.parse::<u8>() {
+        Ok(prefix) if prefix <= max => prefix,
+        _ => return Some(Err(format!("invalid CIDR prefix length: /{prefix}"))),
+    };
+    let (ports, path_prefix) = match rest.strip_prefix(':') {
+        Some(rest) => {
+            let (spec, path) = match rest.find('/') {
+                Some(pos) => (&rest[..pos], Some(rest[pos..].to_string())),
+                None => (rest, None),
+            };
+            match parse_port_range(spec) {
+                Ok(ports) => (Some(ports), path),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        None => (None, Some(rest.to_string()).filter(|path| !path.is_empty())),
+    };
+    Some(Ok(ScopeRule {
+        host: HostPattern::Cidr(addr, prefix),
+        ports,
+        path_prefix,
+    }))
+}
+
+fn cidr_contains(network: IpAddr, prefix: u8, addr: IpAddr) -> bool {
+    match (network, addr) {
+        (IpAddr::V4(network), IpAddr::V4(addr)) => {
+            let mask = u32::MAX.checked_shl(32 - prefix as u32).unwrap_or(0);
+            u32::from(network) & mask == u32::from(addr) & mask
+        }
+        (IpAddr::V6(network), IpAddr::V6(addr)) => {
+            let mask = u128::MAX.checked_shl(128 - prefix as u32).unwrap_or(0);
+            u128::from(network) & mask == u128::from(addr) & mask
+        }
+        _ => false,
+    }
+}
+
+pub fn annotate_record(record: &mut UrlRecord, scope: &Scope) {
+    let in_scope = scope.contains_record(record);
+    record.set_extra("in_scope", in_scope.to_string());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formatter::to_record;
+
+    const SCOPE: &str = "\
+# in scope
+*.example.com
+example.com
+shop.example.org:8443
+api.example.net/v2/
+10.0.0.0/8
+2001:db8::/32
+
+# out of scope
+!admin.example.com
+!*.internal.example.com
+!10.0.5.0/24
+";
+
+    #[test]
+    fn test_host_patterns() {
+        let scope = Scope::parse(SCOPE).unwrap();
+        assert!(scope.contains("example.com", Some(443), "/"));
+        assert!(scope.contains("www.example.com", Some(443), "/"));
+        assert!(!scope.contains("notexample.com", Some(443), "/"));
+        assert!(!scope.contains("admin.example.com", Some(443), "/"));
+        assert!(!scope.contains("db.internal.example.com", Some(443), "/"));
+    }
+
+    #[test]
+    fn test_ports_and_paths() {
+        let scope = Scope::parse(SCOPE).unwrap();
+        assert!(scope.contains("shop.example.org", Some(8443), "/"));
+        assert!(!scope.contains("shop.example.org", Some(443), "/"));
+        assert!(scope.contains("api.example.net", Some(443), "/v2/users"));
+        assert!(!scope.contains("api.example.net", Some(443), "/v1/users"));
+    }
+
+    #[test]
+    fn test_cidr_ranges() {
+        let scope = Scope::parse(SCOPE).unwrap();
+        assert!(scope.contains("10.1.2.3", Some(80), "/"));
+        assert!(!scope.contains("10.0.5.7", Some(80), "/"));
+        assert!(!scope.contains("192.168.0.1", Some(80), "/"));
+        assert!(scope.contains("[2001:db8::1]", Some(443), "/"));
+        assert!(!scope.contains("[2001:db9::1]", Some(443), "/"));
+    }
+
+    #[test]
+    fn test_cidr_ports_and_paths() {
+        let scope = Scope::parse("10.0.0.0/8:443\n[2001:db8::]/32:8000-8100/api/").unwrap();
+        assert!(scope.contains("10.1.2.3", Some(443), "/"));
+        assert!(!scope.contains("10.1.2.3", Some(80), "/"));
+        assert!(scope.contains("[2001:db8::1]", Some(8080), "/api/v1"));
+        assert!(!scope.contains("[2001:db8::1]", Some(8080), "/"));
+
+        let scope = Scope::parse("10.0.0.1/admin/").unwrap();
+        assert!(scope.contains("10.0.0.1", Some(80), "/admin/users"));
+        assert!(!scope.contains("10.0.0.1", Some(80), "/"));
+
+        assert!(Scope::parse("10.0.0.0/33").is_err());
+        assert!(Scope::parse("10.0.0.0/8:http").is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Scope::parse("example.com\nfoo.*.com\n").unwrap_err();
+        assert_eq!(err.line, 2);
+
+        assert!(Scope::parse("example.com:99999").is_err());
+        assert!(Scope::parse("example.com:9000-8000").is_err());
+    }
+
+    #[test]
+    fn test_contains_record_uses_default_port() {
+        let scope = Scope::parse("example.com:443").unwrap();
+        let record = to_record("https://example.com/").unwrap();
+        assert!(scope.contains_record(&record));
+
+        let record = to_record("http://example.com/").unwrap();
+        assert!(!scope.contains_record(&record));
+    }
+}
@@ -0,0 +1,182 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::input::{InputError, InputRecord, LineCursor};
+
+/// Reads the `<url>` entries of a sitemap or the `<sitemap>` entries of a
+/// sitemap index. The `kind` field tells them apart (`url` or `sitemap`);
+/// gzipped sitemaps are decompressed before they get here. Elements from
+/// extension namespaces such as `<image:loc>` are ignored.
+pub fn parse_sitemap(input: &str) -> Result<Vec<Result<InputRecord, InputError>>, InputError> {
+    let mut reader = Reader::from_str(input);
+    let mut lines = LineCursor::new(input);
+    reader.config_mut().trim_text(true);
+
+    let mut records = Vec::new();
+    let mut entry: Option<SitemapEntry> = None;
+    let mut element = String::new();
+    let mut root_seen = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(start)) => {
+                let name = String::from_utf8_lossy(start.name().as_ref()).into_owned();
+                if !root_seen {
+                    root_seen = true;
+                    if name != "urlset" && name != "sitemapindex" {
+                        return Err(InputError {
+                            line: lines.line_at(reader.buffer_position() as usize),
+                            message: format!("expected <urlset> or <sitemapindex>, found <{name}>"),
+                        });
+                    }
+                }
+                if name == "url" || name == "sitemap" {
+                    entry = Some(SitemapEntry {
+                        kind: if name == "url" { "url" } else { "sitemap" },
+                        line: lines.line_at(reader.buffer_position() as usize),
+                        ..SitemapEntry::default()
+                    });
+                }
+                element = name;
+            }
+            Ok(Event::Text(text)) => {
+                let value = text.unescape().map_err(|e| InputError {
+                    line: lines.line_at(reader.buffer_position() as usize),
+                    message: format!("invalid XML: {e}"),
+                })?;
+                if let Some(entry) = entry.as_mut() {
+                    entry.set(&element, &value);
+                }
+            }
+            Ok(Event::CData(data)) => {
+                let value = String::from_utf8_lossy(&data);
+                if let Some(entry) = entry.as_mut() {
+                    entry.set(&element, &value);
+                }
+            }
+            Ok(Event::End(end)) => {
+                let name = end.name();
+                if name.as_ref() == b"url" || name.as_ref() == b"sitemap" {
+                    if let Some(entry) = entry.take() {
+                        records.push(entry.into_record());
+                    }
+                }
+                element.clear();
+            }
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(e) => {
+                return Err(InputError {
+                    line: lines.line_at(reader.error_position() as usize),
+                    message: format!("invalid XML: {e}"),
+                })
+            }
+        }
+    }
+    Ok(records)
+}
+
+#[derive(Default)]
+struct SitemapEntry {
+    kind: &'static str,
+    line: usize,
+    loc: String,
+    lastmod: String,
+    changefreq: String,
+    priority: String,
+}
+
+impl SitemapEntry {
+    fn set(&mut self, element: &str, value: &str) {
+        let slot = match element {
+            "loc" => &mut self.loc,
+            "lastmod" => &mut self.lastmod,
+            "changefreq" => &mut self.changefreq,
+            "priority" => &mut self.priority,
+            _ => return,
+        };
+        slot.push_str(value.trim());
+    }
+
+    fn into_record(self) -> Result<InputRecord, InputError> {
+        if self.loc.is_empty() {
+            return Err(InputError {
+                line: self.line,
+                message: format!("<{}> has no <loc>", self.kind),
+            });
+        }
+        let mut record = InputRecord::new(self.loc).at_line(self.line, None);
+        record.push_field("kind", self.kind);
+        for (name, value) in [
+            ("lastmod", self.lastmod),
+            ("changefreq", self.changefreq),
+            ("priority", self.priority),
+        ] {
+            if !value.is_empty() {
+                record.push_field(name, value);
+            }
+        }
+        Ok(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_urlset() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9"
+        xmlns:image="http://www.google.com/schemas/sitemap-image/1.1">
+  <url>
+    <loc>https://example.com/?a=1&amp;b=2</loc>
+    <lastmod>2024-05-01</lastmod>
+    <changefreq>daily</changefreq>
+    <priority>0.8</priority>
+    <image:image><image:loc>https://example.com/logo.png</image:loc></image:image>
+  </url>
+  <url>
+    <loc>https://example.com/about</loc>
+  </url>
+  <url><lastmod>2024-01-01</lastmod></url>
+</urlset>"#;
+        let records = parse_sitemap(xml).unwrap();
+        assert_eq!(records.len(), 3);
+
+        let first = records[0].as_ref().unwrap();
+        assert_eq!(first.url, "https://example.com/?a=1&b=2");
+        assert_eq!(first.field("kind"), Some("url"));
+        assert_eq!(first.field("lastmod"), Some("2024-05-01"));
+        assert_eq!(first.field("changefreq"), Some("daily"));
+        assert_eq!(first.field("priority"), Some("0.8"));
+        assert_eq!(first.line, Some(4));
+
+        let second = records[1].as_ref().unwrap();
+        assert_eq!(second.url, "https://example.com/about");
+        assert_eq!(second.field("lastmod"), None);
+
+        assert_eq!(records[2].as_ref().unwrap_err().line, 14);
+    }
+
+    #[test]
+    fn test_parse_sitemap_index() {
+        let xml = r#"<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <sitemap>
+    <loc>https://example.com/sitemap-posts.xml.gz</loc>
+    <lastmod>2024-05-01T10:00:00+00:00</lastmod>
+  </sitemap>
+</sitemapindex>"#;
+        let records = parse_sitemap(xml).unwrap();
+        let entry = records[0].as_ref().unwrap();
+        assert_eq!(entry.url, "https://example.com/sitemap-posts.xml.gz");
+        assert_eq!(entry.field("kind"), Some("sitemap"));
+        assert_eq!(entry.field("lastmod"), Some("2024-05-01T10:00:00+00:00"));
+    }
+
+    #[test]
+    fn test_parse_sitemap_rejects_other_documents() {
+        let err = parse_sitemap("<html><body></body></html>").unwrap_err();
+        assert!(err.message.contains("<html>"));
+    }
+}
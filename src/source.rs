@@ -0,0 +1,169 @@
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use crate::error::AppError;
+
+/// Where input text is read from: a file, or stdin when named `-`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputSource {
+    pub name: String,
+    path: Option<PathBuf>,
+}
+
+impl InputSource {
+    pub fn stdin() -> Self {
+        Self {
+            name: "-".to_string(),
+            path: None,
+        }
+    }
+
+    pub fn file(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        Self {
+            name: path.display().to_string(),
+            path: Some(path),
+        }
+    }
+
+    pub fn is_stdin(&self) -> bool {
+        self.path.is_none()
+    }
+
+    /// Reads the whole source, decompressing gzip, zstd and bzip2 data.
+    pub fn read_bytes(&self) -> Result<Vec<u8>, AppError> {
+        let mut bytes = Vec::new();
+        match &self.path {
+            Some(path) => File::open(path)?.read_to_end(&mut bytes)?,
+            None => io::stdin().lock().read_to_end(&mut bytes)?,
+        };
+        Ok(decompress(bytes)?)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Zstd,
+    Bzip2,
+}
+
+/// Identifies compressed data by its magic bytes rather than the file name,
+/// so piped and misnamed files are handled too.
+pub fn detect_compression(bytes: &[u8]) -> Compression {
+    match bytes {
+        [0x1f, 0x8b, ..] => Compression::Gzip,
+        [0x28, 0xb5, 0x2f, 0xfd, ..] => Compression::Zstd,
+        [b'B', b'Z', b'h', b'1'..=b'9', ..] => Compression::Bzip2,
+        _ => Compression::None,
+    }
+}
+
+pub fn decompress(bytes: Vec<u8>) -> io::Result<Vec<u8>> {
+    let mut output = Vec::new();
+    match detect_compression(&bytes) {
+        Compression::None => return Ok(bytes),
+        Compression::Gzip => {
+            flate2::read::MultiGzDecoder::new(bytes.as_slice()).read_to_end(&mut output)?
+        }
+        Compression::Zstd => {
+            zstd::stream::read::Decoder::new(bytes.as_slice())?.read_to_end(&mut output)?
+        }
+        Compression::Bzip2 => {
+            bzip2::read::MultiBzDecoder::new(bytes.as_slice()).read_to_end(&mut output)?
+        }
+    };
+    Ok(output)
+}
+
+/// Expands input arguments into sources. `-` is stdin, an existing file is
+/// read as-is even if its name contains glob characters, other arguments
+/// containing `*`, `?` or `[` are glob patterns that must match at least one
+/// file, and anything else is taken as a path.
+pub fn expand_inputs(arguments: &[String]) -> Result<Vec<InputSource>, AppError> {
+    let mut sources = Vec::new();
+    for argument in arguments {
+        if argument == "-" {
+            sources.push(InputSource::stdin());
+        } else if argument.contains(['*', '?', '[']) && !Path::new(argument).exists() {
+            let paths = glob::glob(argument).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid pattern {argument}: {e}"),
+                )
+            })?;
+            let mut matched: Vec<PathBuf> = paths
+                .filter_map(Result::ok)
+                .filter(|path| path.is_file())
+                .collect();
+            if matched.is_empty() {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("no files match {argument}"),
+                )
+                .into());
+            }
+            matched.sort();
+            sources.extend(matched.into_iter().map(InputSource::file));
+        } else {
+            sources.push(InputSource::file(argument));
+        }
+    }
+    Ok(sources)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_detect_compression() {
+        assert_eq!(detect_compression(b"https://a.com"), Compression::None);
+        assert_eq!(detect_compression(&[0x1f, 0x8b, 0x08]), Compression::Gzip);
+        assert_eq!(
+            detect_compression(&[0x28, 0xb5, 0x2f, 0xfd, 0x00]),
+            Compression::Zstd
+        );
+        assert_eq!(detect_compression(b"BZh91AY&SY"), Compression::Bzip2);
+        assert_eq!(detect_compression(b"BZh"), Compression::None);
+    }
+
+    #[test]
+    fn test_decompress_round_trips() {
+        let text = b"https://example.com/\nhttps://example.org/\n".to_vec();
+
+        let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        gzip.write_all(&text).unwrap();
+        assert_eq!(decompress(gzip.finish().unwrap()).unwrap(), text);
+
+        let zstd = zstd::stream::encode_all(text.as_slice(), 0).unwrap();
+        assert_eq!(decompress(zstd).unwrap(), text);
+
+        let mut bzip2 = bzip2::write::BzEncoder::new(Vec::new(), bzip2::Compression::fast());
+        bzip2.write_all(&text).unwrap();
+        assert_eq!(decompress(bzip2.finish().unwrap()).unwrap(), text);
+
+        assert_eq!(decompress(text.clone()).unwrap(), text);
+    }
+
+    #[test]
+    fn test_expand_inputs() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["b.txt", "a.txt", "c.log"] {
+            std::fs::write(dir.path().join(name), "").unwrap();
+        }
+        let pattern = dir.path().join("*.txt").display().to_string();
+        let sources = expand_inputs(&["-".to_string(), pattern]).unwrap();
+
+        assert!(sources[0].is_stdin());
+        assert!(sources[1].name.ends_with("a.txt"));
+        assert!(sources[2].name.ends_with("b.txt"));
+        assert_eq!(sources.len(), 3);
+
+        let missing = dir.path().join("*.csv").display().to_string();
+        assert!(expand_inputs(&[missing]).is_err());
+    }
+}
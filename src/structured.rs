@@ -0,0 +1,439 @@
+use serde_json::Value;
+
+use crate::formatter::{assemble_url, tsv_unescape};
+use crate::input::{InputError, InputRecord};
+
+/// Columns that `--input-format assemble` reads as URL components.
+pub const URL_COMPONENTS: &[&str] = &[
+    "scheme",
+    "username",
+    "password",
+    "host",
+    "hostname",
+    "subdomain",
+    "domain",
+    "port",
+    "path",
+    "query",
+    "fragment",
+];
+
+/// Reads a CSV file with a header row, taking the URL from `url_column` and
+/// passing every other non-empty cell through as a field named after its
+/// column. `url_column` may also be a 1-based column number.
+pub fn parse_csv(
+    input: &str,
+    url_column: &str,
+) -> Result<Vec<Result<InputRecord, InputError>>, InputError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(input.as_bytes());
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| csv_error(input, &e, "invalid CSV header"))?
+        .iter()
+        .map(|h| h.trim().to_string())
+        .collect();
+
+    let url_index = headers
+        .iter()
+        .position(|h| h == url_column)
+        .or_else(|| {
+            url_column
+                .parse::<usize>()
+                .ok()
+                .filter(|n| (1..=headers.len()).contains(n))
+                .map(|n| n - 1)
+        })
+        .ok_or_else(|| InputError {
+            line: 1,
+            message: format!(
+                "no column named {url_column:?} (columns: {})",
+                headers.join(", ")
+            ),
+        })?;
+
+    let records = read_rows(input, &mut reader, "invalid CSV row")
+        .into_iter()
+        .map(|row| {
+            let Row { cells, line, raw } = row?;
+            let url = cells.get(url_index).map(str::trim).unwrap_or_default();
+            if url.is_empty() {
+                return Err(InputError {
+                    line,
+                    message: format!("empty {:?} column", headers[url_index]),
+                });
+            }
+
+            let mut record = InputRecord::new(url).at_line(line, Some(raw));
+            for (index, cell) in cells.iter().enumerate() {
+                let cell = cell.trim();
+                if index == url_index || cell.is_empty() {
+                    continue;
+                }
+                if let Some(name) = headers.get(index).filter(|name| !name.is_empty()) {
+                    record.push_field(name, cell);
+                }
+            }
+            Ok(record)
+        })
+        .collect();
+    Ok(records)
+}
+
+/// Parses `--component-columns`, a comma-separated list of
+/// `component=column` pairs such as `host=server,path=uri`.
+pub fn parse_component_columns(spec: &str) -> Result<Vec<(String, String)>, String> {
+    spec.split(',')
+        .filter(|pair| !pair.trim().is_empty())
+        .map(|pair| {
+            let (component, column) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("expected component=column, found {pair:?}"))?;
+            let component = component.trim();
+            if !URL_COMPONENTS.contains(&component) {
+                return Err(format!(
+                    "unknown component {component:?} (components: {})",
+                    URL_COMPONENTS.join(", ")
+                ));
+            }
+            Ok((component.to_string(), column.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Reads a CSV or TSV table with a header row and assembles a URL from the
+/// component columns of each row, such as tables written by `--format csv
+/// --header`. Columns are matched to components by name unless `columns`
+/// maps them explicitly; cells equal to `null_value` count as empty and TSV
+/// cells are unescaped the way `--format tsv` escapes them. A header with a
+/// tab in it marks a TSV table; otherwise the table is CSV with `delimiter`
+/// and `quote`, as written by `--csv-delimiter` and `--csv-quote`. A stale
+/// `url` column is ignored and other columns pass through as fields.
+pub fn parse_components(
+    input: &str,
+    columns: &[(String, String)],
+    null_value: &str,
+    delimiter: u8,
+    quote: u8,
+) -> Result<Vec<Result<InputRecord, InputError>>, InputError> {
+    let header_line = input.lines().find(|line| !line.trim().is_empty());
+    let tabs = delimiter != b'\t' && header_line.is_some_and(|line| line.contains('\t'));
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .delimiter(if tabs { b'\t' } else { delimiter })
+        .quote(quote)
+        .quoting(!tabs)
+        .from_reader(input.as_bytes());
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| csv_error(input, &e, "invalid header"))?
+        .iter()
+        .map(|h| h.trim().to_string())
+        .collect();
+
+    let mut component_index: Vec<(&str, usize)> = Vec::new();
+    for &component in URL_COMPONENTS {
+        let column = columns
+            .iter()
+            .find(|(name, _)| name == component)
+            .map_or(component, |(_, column)| column.as_str());
+        match headers.iter().position(|h| h == column) {
+            Some(index) => component_index.push((component, index)),
+            None if column != component => {
+                return Err(InputError {
+                    line: 1,
+                    message: format!(
+                        "no column named {column:?} for {component} (columns: {})",
+                        headers.join(", ")
+                    ),
+                })
+            }
+            None => {}
+        }
+    }
+    let has_host = component_index
+        .iter()
+        .any(|(component, _)| matches!(*component, "host" | "hostname" | "domain"));
+    if !has_host {
+        return Err(InputError {
+            line: 1,
+            message: format!(
+                "no host, hostname or domain column (columns: {})",
+                headers.join(", ")
+            ),
+        });
+    }
+
+    let records = read_rows(input, &mut reader, "invalid row")
+        .into_iter()
+        .map(|row| {
+            let Row { cells, line, raw } = row?;
+            let cells: Vec<Option<String>> = cells
+                .iter()
+                .map(str::trim)
+                .map(|cell| {
+                    if cell.is_empty() || cell == null_value {
+                        None
+                    } else if tabs {
+                        Some(tsv_unescape(cell))
+                    } else {
+                        Some(cell.to_string())
+                    }
+                })
+                .collect();
+            let cell = |index: usize| cells.get(index).and_then(Option::as_deref);
+            let component = |name: &str| {
+                component_index
+                    .iter()
+                    .find(|(component, _)| *component == name)
+                    .and_then(|&(_, index)| cell(index))
+            };
+            let url = match assemble_url(component) {
+                Ok(url) => url,
+                Err(message) => return Err(InputError { line, message }),
+            };
+
+            let mut record = InputRecord::new(url).at_line(line, Some(raw));
+            for (index, name) in headers.iter().enumerate() {
+                let is_component = component_index.iter().any(|&(_, i)| i == index);
+                if is_component || name.is_empty() || name == "url" {
+                    continue;
+                }
+                if let Some(value) = cell(index) {
+                    record.push_field(name, value);
+                }
+            }
+            Ok(record)
+        })
+        .collect();
+    Ok(records)
+}
+
+/// Reads one JSON object per line, taking the URL from the dotted path
+/// `url_field` (e.g. `request.url`). Every other scalar is passed through
+/// under its dotted path; arrays are kept as JSON text.
+pub fn parse_jsonl_line(line: &str, url_field: &str) -> Result<InputRecord, String> {
+    let value: Value = serde_json::from_str(line).map_err(|e| format!("invalid JSON: {e}"))?;
+    if !value.is_object() {
+        return Err("expected a JSON object".to_string());
+    }
+
+    let mut fields = Vec::new();
+    flatten(&value, "", &mut fields);
+    let position = fields
+        .iter()
+        .position(|(name, _)| name == url_field)
+        .ok_or_else(|| format!("missing {url_field:?} field"))?;
+    let (_, url) = fields.remove(position);
+
+    let mut record = InputRecord::new(url);
+    for (name, value) in fields {
+        record.push_field(&name, value);
+    }
+    Ok(record)
+}
+
+fn flatten(value: &Value, prefix: &str, fields: &mut Vec<(String, String)>) {
+    let text = match value {
+        Value::Object(object) => {
+            for (key, value) in object {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten(value, &path, fields);
+            }
+            return;
+        }
+        Value::Null => return,
+        Value::String(s) if s.is_empty() => return,
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    fields.push((prefix.to_string(), text));
+}
+
+/// A data row with the line it starts on and its source text, which spans
+/// several lines when a quoted cell contains line breaks.
+struct Row<'a> {
+    cells: csv::StringRecord,
+    line: usize,
+    raw: &'a str,
+}
+
+/// Reads the remaining rows of `reader`, skipping rows whose cells are all
+/// blank.
+fn read_rows<'a>(
+    input: &'a str,
+    reader: &mut csv::Reader<&[u8]>,
+    context: &str,
+) -> Vec<Result<Row<'a>, InputError>> {
+    let mut rows = Vec::new();
+    let mut cells = csv::StringRecord::new();
+    loop {
+        match reader.read_record(&mut cells) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => {
+                rows.push(Err(csv_error(input, &e, context)));
+                continue;
+            }
+        }
+        if cells.iter().all(|cell| cell.trim().is_empty()) {
+            continue;
+        }
+        let (start, line) = cells.position().map_or((0, 0), |p| row_start(input, p));
+        let end = (reader.position().byte() as usize).clamp(start, input.len());
+        rows.push(Ok(Row {
+            cells: cells.clone(),
+            line,
+            raw: input[start..end].trim_end_matches(['\r', '\n']),
+        }));
+    }
+    rows
+}
+
+/// The csv crate reports a row as starting before any blank lines it skipped,
+/// so step over them to find the offset and line the row actually starts on.
+fn row_start(input: &str, position: &csv::Position) -> (usize, usize) {
+    let start = (position.byte() as usize).min(input.len());
+    let rest = &input[start..];
+    let skipped = rest.len() - rest.trim_start_matches(['\r', '\n']).len();
+    let newlines = rest[..skipped].matches('\n').count();
+    (start + skipped, position.line() as usize + newlines)
+}
+
+fn csv_error(input: &str, error: &csv::Error, context: &str) -> InputError {
+    InputError {
+        line: error.position().map_or(0, |p| row_start(input, p).1),
+        message: format!("{context}: {error}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv_passes_other_columns_through() {
+        let csv = "id,referrer,note\n\
+                   1,https://example.com/a,\"hello, world\"\n\
+                   \n\
+                   2,,missing\n";
+        let records = parse_csv(csv, "referrer").unwrap();
+        assert_eq!(records.len(), 2);
+
+        let first = records[0].as_ref().unwrap();
+        assert_eq!(first.url, "https://example.com/a");
+        assert_eq!(first.field("id"), Some("1"));
+        assert_eq!(first.field("note"), Some("hello, world"));
+        assert_eq!(first.field("referrer"), None);
+
+        assert_eq!(first.line, Some(2));
+        assert_eq!(
+            first.raw.as_deref(),
+            Some("1,https://example.com/a,\"hello, world\"")
+        );
+
+        let err = records[1].as_ref().unwrap_err();
+        assert_eq!(err.line, 4);
+    }
+
+    #[test]
+    fn test_parse_csv_multiline_row() {
+        let csv = "url,note\r\n\
+                   https://example.com/a,\"line one\r\nline two\"\r\n\
+                   \r\n\
+                   https://example.com/b,x\r\n";
+        let records = parse_csv(csv, "url").unwrap();
+
+        let first = records[0].as_ref().unwrap();
+        assert_eq!(first.line, Some(2));
+        assert_eq!(
+            first.raw.as_deref(),
+            Some("https://example.com/a,\"line one\r\nline two\"")
+        );
+
+        let second = records[1].as_ref().unwrap();
+        assert_eq!(second.line, Some(5));
+        assert_eq!(second.raw.as_deref(), Some("https://example.com/b,x"));
+    }
+
+    #[test]
+    fn test_parse_csv_column_number() {
+        let records = parse_csv("a,b\nx,https://example.com\n", "2").unwrap();
+        assert_eq!(records[0].as_ref().unwrap().url, "https://example.com");
+    }
+
+    #[test]
+    fn test_parse_csv_unknown_column() {
+        let err = parse_csv("a,b\n1,2\n", "url").unwrap_err();
+        assert!(err.message.contains("columns: a, b"));
+    }
+
+    #[test]
+    fn test_parse_components_tsv() {
+        let tsv = "url\tscheme\thostname\tport\tpath\tquery\tstatus\n\
+                   stale\thttps\texample.com\t443\t/a b\\tc\t\\N\t200\n\
+                   stale\thttp\t::1\t8080\t\\N\tx=1\t\\N\n\
+                   stale\thttp\t\\N\t\\N\t/\t\\N\t\\N\n";
+        let records = parse_components(tsv, &[], "\\N", b',', b'"').unwrap();
+
+        let first = records[0].as_ref().unwrap();
+        assert_eq!(first.url, "https://example.com/a%20b%09c");
+        assert_eq!(first.field("status"), Some("200"));
+        assert_eq!(first.field("url"), None);
+        assert_eq!(first.line, Some(2));
+
+        let second = records[1].as_ref().unwrap();
+        assert_eq!(second.url, "http://[::1]:8080/?x=1");
+
+        let err = records[2].as_ref().unwrap_err();
+        assert_eq!(err.message, "missing host");
+        assert_eq!(err.line, 4);
+    }
+
+    #[test]
+    fn test_parse_components_mapped_columns() {
+        let csv = "proto,server,uri\nhttps,www.example.com,\"/search?q=a,b\"\n";
+        let columns = parse_component_columns("scheme=proto,host=server,path=uri").unwrap();
+        let records = parse_components(csv, &columns, "", b',', b'"').unwrap();
+        assert_eq!(
+            records[0].as_ref().unwrap().url,
+            "https://www.example.com/search%3Fq=a,b"
+        );
+
+        assert!(parse_component_columns("server=host").is_err());
+        let columns = parse_component_columns("host=machine").unwrap();
+        assert!(parse_components(csv, &columns, "", b',', b'"').is_err());
+        assert!(parse_components("a,b\n1,2\n", &[], "", b',', b'"').is_err());
+    }
+
+    #[test]
+    fn test_parse_components_custom_delimiter() {
+        let csv = "scheme;host;path\nhttps;example.com;'/a;b'\n";
+        let records = parse_components(csv, &[], "", b';', b'\'').unwrap();
+        assert_eq!(records[0].as_ref().unwrap().url, "https://example.com/a;b");
+    }
+
+    #[test]
+    fn test_parse_jsonl_line_nested_field() {
+        let record = parse_jsonl_line(
+            r#"{"ts": 1700000000, "request": {"url": "https://example.com/", "method": "GET"}, "tags": ["a"], "user": null}"#,
+            "request.url",
+        )
+        .unwrap();
+        assert_eq!(record.url, "https://example.com/");
+        assert_eq!(record.field("request.method"), Some("GET"));
+        assert_eq!(record.field("ts"), Some("1700000000"));
+        assert_eq!(record.field("tags"), Some(r#"["a"]"#));
+        assert_eq!(record.field("user"), None);
+    }
+
+    #[test]
+    fn test_parse_jsonl_line_missing_field() {
+        assert!(parse_jsonl_line(r#"{"link": "https://example.com"}"#, "url").is_err());
+    }
+}
@@ -0,0 +1,262 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::Deserialize;
+
+use crate::input::{push_present, InputError, InputRecord, LineCursor};
+
+#[derive(Deserialize)]
+struct Har {
+    log: HarLog,
+}
+
+#[derive(Deserialize)]
+struct HarLog {
+    #[serde(default)]
+    entries: Vec<HarEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HarEntry {
+    started_date_time: Option<String>,
+    request: HarRequest,
+    response: Option<HarResponse>,
+}
+
+#[derive(Deserialize)]
+struct HarRequest {
+    method: Option<String>,
+    url: String,
+}
+
+#[derive(Deserialize)]
+struct HarResponse {
+    #[serde(default)]
+    status: i64,
+    content: Option<HarContent>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HarContent {
+    mime_type: Option<String>,
+}
+
+/// Reads every request in a HAR 1.2 archive (`log.entries[]`). Entries
+/// without a response, or whose status is 0 because the request never
+/// completed, have no `status` or `mime_type` field.
+pub fn parse_har(input: &str) -> Result<Vec<InputRecord>, InputError> {
+    let har: Har = serde_json::from_str(input).map_err(|e| InputError {
+        line: e.line(),
+        message: format!("invalid HAR: {e}"),
+    })?;
+
+    let records = har
+        .log
+        .entries
+        .into_iter()
+        .map(|entry| {
+            let mut record = InputRecord::new(entry.request.url);
+            push_present(&mut record, "method", entry.request.method.as_deref());
+            if let Some(response) = entry.response.filter(|r| r.status > 0) {
+                record.push_field("status", response.status.to_string());
+                let mime_type = response.content.and_then(|c| c.mime_type);
+                push_present(&mut record, "mime_type", mime_type.as_deref());
+            }
+            push_present(&mut record, "timestamp", entry.started_date_time.as_deref());
+            record
+        })
+        .collect();
+    Ok(records)
+}
+
+/// Reads the `<item>` elements of a Burp Suite "Save items" XML export.
+/// Request and response bodies are ignored, so base64-encoded exports work
+/// the same as plain ones. An item without a URL becomes a per-record error
+/// at the item's line; malformed XML fails the whole file.
+pub fn parse_burp(input: &str) -> Result<Vec<Result<InputRecord, InputError>>, InputError> {
+    let mut reader = Reader::from_str(input);
+    let mut lines = LineCursor::new(input);
+    reader.config_mut().trim_text(true);
+
+    let mut records = Vec::new();
+    let mut item: Option<BurpItem> = None;
+    let mut element = String::new();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(start)) => {
+                let name = String::from_utf8_lossy(start.name().as_ref()).into_owned();
+                if name == "item" {
+                    item = Some(BurpItem {
+                        line: lines.line_at(reader.buffer_position() as usize),
+                        ..BurpItem::default()
+                    });
+                }
+                element = name;
+            }
+            Ok(Event::Text(text)) => {
+                let value = text.unescape().map_err(|e| InputError {
+                    line: lines.line_at(reader.buffer_position() as usize),
+                    message: format!("invalid XML: {e}"),
+                })?;
+                if let Some(item) = item.as_mut() {
+                    item.set(&element, &value);
+                }
+            }
+            Ok(Event::CData(data)) => {
+                let value = String::from_utf8_lossy(&data);
+                if let Some(item) = item.as_mut() {
+                    item.set(&element, &value);
+                }
+            }
+            Ok(Event::End(end)) => {
+                if end.name().as_ref() == b"item" {
+                    if let Some(item) = item.take() {
+                        records.push(item.into_record());
+                    }
+                }
+                element.clear();
+            }
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(e) => {
+                return Err(InputError {
+                    line: lines.line_at(reader.error_position() as usize),
+                    message: format!("invalid XML: {e}"),
+                })
+            }
+        }
+    }
+    Ok(records)
+}
+
+#[derive(Default)]
+struct BurpItem {
+    line: usize,
+    url: Option<String>,
+    method: Option<String>,
+    status: Option<String>,
+    mime_type: Option<String>,
+    time: Option<String>,
+}
+
+impl BurpItem {
+    fn set(&mut self, element: &str, value: &str) {
+        let slot = match element {
+            "url" => &mut self.url,
+            "method" => &mut self.method,
+            "status" => &mut self.status,
+            "mimetype" => &mut self.mime_type,
+            "time" => &mut self.time,
+            _ => return,
+        };
+        slot.get_or_insert_with(String::new).push_str(value);
+    }
+
+    fn into_record(self) -> Result<InputRecord, InputError> {
+        let url = self.url.filter(|u| !u.is_empty()).ok_or(InputError {
+            line: self.line,
+            message: "<item> has no <url>".to_string(),
+        })?;
+        let mut record = InputRecord::new(url).at_line(self.line, None);
+        push_present(&mut record, "method", self.method.as_deref());
+        push_present(&mut record, "status", self.status.as_deref());
+        push_present(&mut record, "mime_type", self.mime_type.as_deref());
+        push_present(&mut record, "timestamp", self.time.as_deref());
+        Ok(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HAR: &str = r#"{
+  "log": {
+    "version": "1.2",
+    "entries": [
+      {
+        "startedDateTime": "2024-05-01T10:00:00.000Z",
+        "request": {"method": "GET", "url": "https://example.com/app.js"},
+        "response": {"status": 200, "content": {"mimeType": "application/javascript"}}
+      },
+      {
+        "request": {"method": "POST", "url": "https://api.example.com/login"},
+        "response": {"status": 0, "content": {}}
+      }
+    ]
+  }
+}"#;
+
+    #[test]
+    fn test_parse_har() {
+        let records = parse_har(HAR).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].url, "https://example.com/app.js");
+        assert_eq!(records[0].field("method"), Some("GET"));
+        assert_eq!(records[0].field("status"), Some("200"));
+        assert_eq!(
+            records[0].field("mime_type"),
+            Some("application/javascript")
+        );
+        assert_eq!(
+            records[0].field("timestamp"),
+            Some("2024-05-01T10:00:00.000Z")
+        );
+        assert_eq!(records[1].field("method"), Some("POST"));
+        assert_eq!(records[1].field("status"), None);
+    }
+
+    #[test]
+    fn test_parse_har_error_line() {
+        let err = parse_har("{\n  \"log\": {\n    \"entries\": [1]\n  }\n}").unwrap_err();
+        assert_eq!(err.line, 3);
+    }
+
+    #[test]
+    fn test_parse_burp() {
+        let xml = r#"<?xml version="1.0"?>
+<items burpVersion="2024.1">
+  <item>
+    <time>Wed May 01 10:00:00 UTC 2024</time>
+    <url><![CDATA[https://example.com/search?q=a&b=c]]></url>
+    <host ip="93.184.216.34">example.com</host>
+    <method><![CDATA[GET]]></method>
+    <request base64="true"><![CDATA[R0VUIC8=]]></request>
+    <status>200</status>
+    <mimetype>HTML</mimetype>
+  </item>
+  <item>
+    <url><![CDATA[https://example.com/favicon.ico]]></url>
+    <method><![CDATA[GET]]></method>
+    <status></status>
+  </item>
+</items>"#;
+        let records: Vec<_> = parse_burp(xml)
+            .unwrap()
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].url, "https://example.com/search?q=a&b=c");
+        assert_eq!(records[0].field("status"), Some("200"));
+        assert_eq!(records[0].field("mime_type"), Some("HTML"));
+        assert_eq!(
+            records[0].field("timestamp"),
+            Some("Wed May 01 10:00:00 UTC 2024")
+        );
+        assert_eq!(records[1].field("status"), None);
+        assert_eq!(records[1].line, Some(12));
+    }
+
+    #[test]
+    fn test_parse_burp_item_without_url() {
+        let xml = "<items>\n<item>\n<method>GET</method>\n</item>\n\
+                   <item><url>https://example.com/</url></item>\n</items>";
+        let records = parse_burp(xml).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].as_ref().unwrap_err().line, 2);
+        assert_eq!(records[1].as_ref().unwrap().url, "https://example.com/");
+    }
+}
@@ -0,0 +1,348 @@
+use std::fmt;
+use std::path::Path;
+
+use crate::domain::{extract_domain, read_domain_list};
+use crate::error::AppError;
+use crate::formatter::UrlRecord;
+
+pub const SIMILARITY_FIELDS: &[&str] = &["similar_to", "mutation", "edit_distance"];
+
+/// Generic edits further away than this are not reported as lookalikes.
+pub const MAX_EDIT_DISTANCE: usize = 2;
+
+/// Combosquat detection only applies to watched names at least this long.
+const MIN_COMBOSQUAT_NAME_LEN: usize = 4;
+
+const KEYBOARD_ROWS: [&str; 4] = ["1234567890-", "qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+const HOMOGLYPH_PAIRS: &[(char, char)] = &[
+    ('0', 'o'),
+    ('1', 'l'),
+    ('1', 'i'),
+    ('3', 'e'),
+    ('5', 's'),
+    ('l', 'i'),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mutation {
+    TldSwap,
+    Combosquat,
+    Bitsquat,
+    Homoglyph,
+    KeyboardTypo,
+    Replacement,
+    Transposition,
+    Omission,
+    Repetition,
+    Insertion,
+    EditDistance,
+}
+
+impl Mutation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Mutation::TldSwap => "tld_swap",
+            Mutation::Combosquat => "combosquat",
+            Mutation::Bitsquat => "bitsquat",
+            Mutation::Homoglyph => "homoglyph",
+            Mutation::KeyboardTypo => "keyboard_typo",
+            Mutation::Replacement => "replacement",
+            Mutation::Transposition => "transposition",
+            Mutation::Omission => "omission",
+            Mutation::Repetition => "repetition",
+            Mutation::Insertion => "insertion",
+            Mutation::EditDistance => "edit_distance",
+        }
+    }
+}
+
+impl fmt::Display for Mutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimilarMatch<'a> {
+    pub watched: &'a str,
+    pub mutation: Mutation,
+    pub distance: usize,
+}
+
+/// Registrable domains watched for typosquats.
+#[derive(Debug, Clone, Default)]
+pub struct WatchList {
+    domains: Vec<String>,
+}
+
+impl WatchList {
+    pub fn from_domains<I, S>(domains: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let domains = domains
+            .into_iter()
+            .map(|domain| domain.as_ref().trim().to_lowercase())
+            .filter(|domain| !domain.is_empty())
+            .collect();
+        Self { domains }
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, AppError> {
+        Ok(Self::from_domains(read_domain_list(path)?))
+    }
+
+    /// Returns the closest watched domain that `domain` is a mutation of.
+    pub fn closest(&self, domain: &str) -> Option<SimilarMatch<'_>> {
+        let domain = domain.to_lowercase();
+        self.domains
+            .iter()
+            .filter_map(|watched| {
+                classify(&domain, watched).map(|mutation| SimilarMatch {
+                    watched: watched.as_str(),
+                    mutation,
+                    distance: edit_distance(&domain, watched),
+                })
+            })
+            .min_by_key(|found| found.distance)
+    }
+}
+
+pub fn annotate_record(record: &mut UrlRecord, watch_list: &WatchList) {
+    let Some(domain) = record.domain.clone() else {
+        return;
+    };
+
+    if let Some(found) = watch_list.closest(&domain) {
+        let (watched, mutation, distance) = (
+            found.watched.to_string(),
+            found.mutation.as_str(),
+            found.distance.to_string(),
+        );
+        record.set_extra("similar_to", watched);
+        record.set_extra("mutation", mutation);
+        record.set_extra("edit_distance", distance);
+    }
+}
+
+/// Classifies how `candidate` was derived from `watched`, or `None` if the
+/// two are identical or unrelated.
+pub fn classify(candidate: &str, watched: &str) -> Option<Mutation> {
+    if candidate == watched {
+        return None;
+    }
+
+    let (name, suffix) = split_registrable(candidate);
+    let (watched_name, watched_suffix) = split_registrable(watched);
+
+    if name == watched_name {
+        return Some(Mutation::TldSwap);
+    }
+
+    if suffix == watched_suffix {
+        if let Some(mutation) = classify_name(name, watched_name) {
+            return Some(mutation);
+        }
+    }
+
+    if edit_distance(candidate, watched) <= MAX_EDIT_DISTANCE {
+        Some(Mutation::EditDistance)
+    } else {
+        None
+    }
+}
+
+fn classify_name(name: &str, watched: &str) -> Option<Mutation> {
+    let a: Vec<char> = name.chars().collect();
+    let b: Vec<char> = watched.chars().collect();
+
+    if b.len() >= MIN_COMBOSQUAT_NAME_LEN {
+        let affixed =
+            a.len() > b.len() + 2 && (name.starts_with(watched) || name.ends_with(watched));
+        if affixed || name.split('-').any(|token| token == watched) {
+            return Some(Mutation::Combosquat);
+        }
+    }
+
+    if a.len() == b.len() {
+        let diffs: Vec<usize> = (0..a.len()).filter(|&i| a[i] != b[i]).collect();
+        match diffs.as_slice() {
+            [i] => return Some(classify_replacement(a[*i], b[*i])),
+            [i, j] if *j == i + 1 && a[*i] == b[*j] && a[*j] == b[*i] => {
+                return Some(Mutation::Transposition)
+            }
+            _ => {}
+        }
+    }
+
+    if a.len() + 1 == b.len() && is_single_deletion(&a, &b) {
+        return Some(Mutation::Omission);
+    }
+
+    if a.len() == b.len() + 1 && is_single_deletion(&b, &a) {
+        let inserted = (0..a.len())
+            .find(|&i| i >= b.len() || a[i] != b[i])
+            .unwrap_or(b.len());
+        let repeated = (inserted > 0 && a[inserted - 1] == a[inserted])
+            || (inserted + 1 < a.len() && a[inserted + 1] == a[inserted]);
+        return Some(if repeated {
+            Mutation::Repetition
+        } else {
+            Mutation::Insertion
+        });
+    }
+
+    None
+}
+
+fn classify_replacement(found: char, expected: char) -> Mutation {
+    if HOMOGLYPH_PAIRS
+        .iter()
+        .any(|&(x, y)| (found, expected) == (x, y) || (found, expected) == (y, x))
+    {
+        Mutation::Homoglyph
+    } else if is_bitflip(found, expected) {
+        Mutation::Bitsquat
+    } else if is_keyboard_adjacent(found, expected) {
+        Mutation::KeyboardTypo
+    } else {
+        Mutation::Replacement
+    }
+}
+
+/// True when `shorter` is `longer` with exactly one character removed.
+fn is_single_deletion(shorter: &[char], longer: &[char]) -> bool {
+    let split = (0..shorter.len())
+        .find(|&i| shorter[i] != longer[i])
+        .unwrap_or(shorter.len());
+    shorter[split..] == longer[split + 1..]
+}
+
+fn is_bitflip(a: char, b: char) -> bool {
+    a.is_ascii()
+        && b.is_ascii()
+        && (a as u8 ^ b as u8).count_ones() == 1
+        && (a.is_ascii_alphanumeric() || a == '-')
+}
+
+fn keyboard_position(c: char) -> Option<(i32, i32)> {
+    KEYBOARD_ROWS.iter().enumerate().find_map(|(row, keys)| {
+        keys.find(c.to_ascii_lowercase())
+            .map(|col| (row as i32, col as i32))
+    })
+}
+
+fn is_keyboard_adjacent(a: char, b: char) -> bool {
+    let (Some((ra, ca)), Some((rb, cb))) = (keyboard_position(a), keyboard_position(b)) else {
+        return false;
+    };
+
+    // Rows are staggered: a key touches the two keys below it at the same and
+    // previous column, and the two keys above it at the same and next column.
+    match rb - ra {
+        0 => (ca - cb).abs() == 1,
+        1 => cb == ca || cb == ca - 1,
+        -1 => cb == ca || cb == ca + 1,
+        _ => false,
+    }
+}
+
+fn split_registrable(domain: &str) -> (&str, &str) {
+    let registrable = extract_domain(domain);
+    let start = domain.len() - registrable.len();
+    let registrable = &domain[start..];
+    registrable.split_once('.').unwrap_or((registrable, ""))
+}
+
+/// Optimal string alignment distance (Levenshtein plus adjacent transpositions).
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+
+    rows[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("example.com", "example.com"), 0);
+        assert_eq!(edit_distance("exmaple.com", "example.com"), 1);
+        assert_eq!(edit_distance("examp1e.com", "example.com"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn test_classify_mutations() {
+        let w = "example.com";
+        assert_eq!(classify("example.com", w), None);
+        assert_eq!(classify("examp1e.com", w), Some(Mutation::Homoglyph));
+        assert_eq!(classify("exmaple.com", w), Some(Mutation::Transposition));
+        assert_eq!(classify("example-login.com", w), Some(Mutation::Combosquat));
+        assert_eq!(classify("secureexample.com", w), Some(Mutation::Combosquat));
+        assert_eq!(classify("example.net", w), Some(Mutation::TldSwap));
+        assert_eq!(classify("example.co.uk", w), Some(Mutation::TldSwap));
+        assert_eq!(classify("exampme.com", w), Some(Mutation::Bitsquat));
+        assert_eq!(classify("exanple.com", w), Some(Mutation::KeyboardTypo));
+        assert_eq!(classify("exaple.com", w), Some(Mutation::Omission));
+        assert_eq!(classify("exammple.com", w), Some(Mutation::Repetition));
+        assert_eq!(classify("exaxmple.com", w), Some(Mutation::Insertion));
+        assert_eq!(classify("unrelated.com", w), None);
+    }
+
+    #[test]
+    fn test_keyboard_adjacency() {
+        assert!(is_keyboard_adjacent('g', 'h'));
+        assert!(is_keyboard_adjacent('g', 't'));
+        assert!(is_keyboard_adjacent('g', 'b'));
+        assert!(!is_keyboard_adjacent('g', 'p'));
+    }
+
+    #[test]
+    fn test_watch_list_closest() {
+        let list = WatchList::from_domains(["example.com", "github.com"]);
+        let found = list.closest("githuh.com").unwrap();
+        assert_eq!(found.watched, "github.com");
+        assert_eq!(found.mutation, Mutation::KeyboardTypo);
+        assert_eq!(found.distance, 1);
+
+        assert!(list.closest("github.com").is_none());
+        assert!(list.closest("rust-lang.org").is_none());
+    }
+
+    #[test]
+    fn test_annotate_record() {
+        let list = WatchList::from_domains(["example.com"]);
+        let mut record = crate::formatter::to_record("https://login.exmaple.com/").unwrap();
+        annotate_record(&mut record, &list);
+
+        assert_eq!(record.get_field("similar_to"), Some("example.com"));
+        assert_eq!(record.get_field("mutation"), Some("transposition"));
+        assert_eq!(record.get_field("edit_distance"), Some("1"));
+    }
+}
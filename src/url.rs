@@ -1,6 +1,8 @@
 use std::arch::x86_64::*;
 use std::fmt;
 
+use crate::parser::default_port;
+
 #[inline(always)]
 fn likely(b: bool) -> bool {
     #[cold]
@@ -849,6 +851,56 @@ impl Url {
     pub fn as_str(&self) -> &str {
         &self.input
     }
+
+    /// Byte ranges within [`Url::as_str`] of the components present in the
+    /// URL, in order. Delimiters such as `://`, `@`, `?` and `#` are not part
+    /// of any range; an IPv6 host includes its brackets.
+    pub fn component_ranges(&self) -> Vec<(UrlPart, Range)> {
+        let parts = [
+            (UrlPart::Scheme, SCHEME_IDX, None),
+            (UrlPart::Username, USERNAME_IDX, Some(HAS_USERNAME)),
+            (UrlPart::Password, PASSWORD_IDX, Some(HAS_PASSWORD)),
+            (UrlPart::Host, HOST_IDX, None),
+            (UrlPart::Port, PORT_IDX, Some(HAS_PORT)),
+            (UrlPart::Path, PATH_IDX, None),
+            (UrlPart::Query, QUERY_IDX, Some(HAS_QUERY)),
+            (UrlPart::Fragment, FRAGMENT_IDX, Some(HAS_FRAGMENT)),
+        ];
+        parts
+            .into_iter()
+            .filter(|(_, _, flag)| flag.is_none_or(|flag| self.has_flag(flag)))
+            .map(|(part, idx, _)| (part, self.get_range(idx)))
+            .filter(|(_, range)| !range.is_empty())
+            .collect()
+    }
+}
+
+/// A component of a URL, as located by [`Url::component_ranges`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UrlPart {
+    Scheme,
+    Username,
+    Password,
+    Host,
+    Port,
+    Path,
+    Query,
+    Fragment,
+}
+
+impl UrlPart {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UrlPart::Scheme => "scheme",
+            UrlPart::Username => "username",
+            UrlPart::Password => "password",
+            UrlPart::Host => "host",
+            UrlPart::Port => "port",
+            UrlPart::Path => "path",
+            UrlPart::Query => "query",
+            UrlPart::Fragment => "fragment",
+        }
+    }
 }
 
 impl fmt::Display for Url {
@@ -857,6 +909,154 @@ impl fmt::Display for Url {
     }
 }
 
+/// The components of a URL to be assembled into one. Values are taken as
+/// written: characters that are not allowed in their component are
+/// percent-encoded, while existing `%XX` escapes are left alone so that
+/// already-encoded values are not encoded twice.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UrlParts {
+    pub scheme: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub host: String,
+    pub port: Option<u16>,
+    pub path: String,
+    pub query: Option<String>,
+    pub fragment: Option<String>,
+}
+
+impl UrlParts {
+    /// Writes the components out as a URL string. IPv6 hosts are bracketed,
+    /// a port written into the host (`example.com:8080`) is used unless it
+    /// conflicts with `port`, the scheme's default port is omitted and an
+    /// empty path becomes `/`.
+    pub fn serialize(&self) -> Result<String, UrlParseError> {
+        let scheme = self.scheme.to_ascii_lowercase();
+        let valid_scheme = scheme.bytes().next().is_some_and(CharClass::is_ascii_alpha)
+            && scheme
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b"+-.".contains(&b));
+        if !valid_scheme {
+            return Err(UrlParseError::InvalidScheme);
+        }
+
+        let mut url = format!("{scheme}://");
+        if let Some(username) = self.username.as_deref().filter(|u| !u.is_empty()) {
+            encode_into(&mut url, username, is_userinfo_byte);
+            if let Some(password) = self.password.as_deref().filter(|p| !p.is_empty()) {
+                url.push(':');
+                encode_into(&mut url, password, |b| b == b':' || is_userinfo_byte(b));
+            }
+            url.push('@');
+        }
+
+        let (host, host_port) = split_host_port(self.host.trim())?;
+        let port = match (self.port, host_port) {
+            (Some(port), Some(other)) if port != other => return Err(UrlParseError::InvalidPort),
+            (port, other) => port.or(other),
+        };
+        let bare = host
+            .strip_prefix('[')
+            .and_then(|h| h.strip_suffix(']'))
+            .unwrap_or(host);
+        if bare.contains(':') {
+            let valid_ipv6 = bare
+                .bytes()
+                .all(|b| b.is_ascii_hexdigit() || b == b':' || b == b'.');
+            if !valid_ipv6 {
+                return Err(UrlParseError::InvalidHost);
+            }
+            url.push('[');
+            url.push_str(&bare.to_ascii_lowercase());
+            url.push(']');
+        } else if bare.is_empty() {
+            return Err(UrlParseError::InvalidHost);
+        } else {
+            if let Some(ch) = bare.chars().find(|&c| is_forbidden_host_char(c)) {
+                return Err(UrlParseError::InvalidCharacter(ch));
+            }
+            url.push_str(bare);
+        }
+
+        if let Some(port) = port.filter(|&p| Some(p) != default_port(&scheme)) {
+            url.push(':');
+            url.push_str(&port.to_string());
+        }
+
+        if !self.path.starts_with('/') {
+            url.push('/');
+        }
+        encode_into(&mut url, &self.path, |b| {
+            b == b'/' || b == b':' || b == b'@' || is_userinfo_byte(b)
+        });
+
+        if let Some(query) = self.query.as_deref().filter(|q| !q.is_empty()) {
+            url.push('?');
+            encode_into(&mut url, query, is_query_byte);
+        }
+        if let Some(fragment) = self.fragment.as_deref().filter(|f| !f.is_empty()) {
+            url.push('#');
+            encode_into(&mut url, fragment, is_query_byte);
+        }
+        Ok(url)
+    }
+
+    /// Serializes the components and parses the result, so the assembled URL
+    /// is known to read back the same way.
+    pub fn to_url(&self) -> Result<Url, UrlParseError> {
+        Url::parse(&self.serialize()?)
+    }
+}
+
+/// Splits off a port written into the host, as in `example.com:8080` or
+/// `[::1]:8080`. An unbracketed host with more than one colon is an IPv6
+/// address and is left whole.
+fn split_host_port(host: &str) -> Result<(&str, Option<u16>), UrlParseError> {
+    let (host, port) = if host.starts_with('[') {
+        match host.rsplit_once("]:") {
+            Some((address, port)) => (&host[..address.len() + 1], Some(port)),
+            None => (host, None),
+        }
+    } else {
+        match host.split_once(':') {
+            Some((name, port)) if !port.contains(':') => (name, Some(port)),
+            _ => (host, None),
+        }
+    };
+    let port = port
+        .map(|port| port.parse().map_err(|_| UrlParseError::InvalidPort))
+        .transpose()?;
+    Ok((host, port))
+}
+
+/// Unreserved and sub-delimiter characters from RFC 3986, which are allowed
+/// unencoded in every component.
+fn is_userinfo_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=".contains(&b)
+}
+
+fn is_query_byte(b: u8) -> bool {
+    b"/?:@".contains(&b) || is_userinfo_byte(b)
+}
+
+fn is_forbidden_host_char(c: char) -> bool {
+    c.is_whitespace() || c.is_control() || " /?#@[]\\<>^`{|}%".contains(c)
+}
+
+fn encode_into(out: &mut String, value: &str, keep: fn(u8) -> bool) {
+    let bytes = value.as_bytes();
+    for (i, &b) in bytes.iter().enumerate() {
+        let escape = b == b'%'
+            && bytes.get(i + 1).is_some_and(u8::is_ascii_hexdigit)
+            && bytes.get(i + 2).is_some_and(u8::is_ascii_hexdigit);
+        if keep(b) || escape {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{b:02X}"));
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -944,6 +1144,103 @@ mod tests {
         assert_eq!(url.fragment(), None);
     }
 
+    #[test]
+    fn test_component_ranges() {
+        let input = "https://user:pw@[::1]:8080/a/b?q=1#top";
+        let url = Url::parse(input).unwrap();
+        let parts: Vec<(&str, &str)> = url
+            .component_ranges()
+            .into_iter()
+            .map(|(part, range)| (part.as_str(), &input[range.start()..range.end()]))
+            .collect();
+        assert_eq!(
+            parts,
+            [
+                ("scheme", "https"),
+                ("username", "user"),
+                ("password", "pw"),
+                ("host", "[::1]"),
+                ("port", "8080"),
+                ("path", "/a/b"),
+                ("query", "q=1"),
+                ("fragment", "top"),
+            ]
+        );
+
+        let url = Url::parse("http://example.com").unwrap();
+        let parts: Vec<UrlPart> = url.component_ranges().into_iter().map(|(p, _)| p).collect();
+        assert_eq!(parts, [UrlPart::Scheme, UrlPart::Host]);
+    }
+
+    #[test]
+    fn test_serialize_parts() {
+        let parts = UrlParts {
+            scheme: "HTTPS".to_string(),
+            username: Some("a b".to_string()),
+            password: Some("p@ss:1".to_string()),
+            host: "example.com".to_string(),
+            port: Some(443),
+            path: "docs/ünï code/%41".to_string(),
+            query: Some("q=a b&x=#1".to_string()),
+            fragment: Some("top section".to_string()),
+        };
+        assert_eq!(
+            parts.serialize().unwrap(),
+            "https://a%20b:p%40ss:1@example.com/docs/%C3%BCn%C3%AF%20code/%41\
+             ?q=a%20b&x=%231#top%20section"
+        );
+    }
+
+    #[test]
+    fn test_serialize_parts_ipv6_and_ports() {
+        let mut parts = UrlParts {
+            scheme: "http".to_string(),
+            host: "::1".to_string(),
+            port: Some(8080),
+            ..UrlParts::default()
+        };
+        let url = parts.to_url().unwrap();
+        assert_eq!(url.as_str(), "http://[::1]:8080/");
+        assert_eq!(url.port(), Some(8080));
+
+        parts.host = "[2001:DB8::1]".to_string();
+        parts.port = Some(80);
+        assert_eq!(parts.serialize().unwrap(), "http://[2001:db8::1]/");
+        parts.host = "[::1]:8080".to_string();
+        parts.port = None;
+        assert_eq!(parts.serialize().unwrap(), "http://[::1]:8080/");
+
+        parts.host = "example.com:8080".to_string();
+        assert_eq!(parts.serialize().unwrap(), "http://example.com:8080/");
+        parts.port = Some(8080);
+        assert_eq!(parts.serialize().unwrap(), "http://example.com:8080/");
+        parts.port = Some(9090);
+        assert_eq!(parts.serialize(), Err(UrlParseError::InvalidPort));
+        parts.host = "example.com:http".to_string();
+        assert_eq!(parts.serialize(), Err(UrlParseError::InvalidPort));
+    }
+
+    #[test]
+    fn test_serialize_parts_rejects_invalid_components() {
+        let parts = UrlParts {
+            scheme: "http".to_string(),
+            host: "exa mple.com".to_string(),
+            ..UrlParts::default()
+        };
+        assert_eq!(parts.serialize(), Err(UrlParseError::InvalidCharacter(' ')));
+
+        let parts = UrlParts {
+            scheme: "1http".to_string(),
+            host: "example.com".to_string(),
+            ..UrlParts::default()
+        };
+        assert_eq!(parts.serialize(), Err(UrlParseError::InvalidScheme));
+        assert_eq!(
+            UrlParts::default().serialize(),
+            Err(UrlParseError::InvalidScheme)
+        );
+    }
+
     #[test]
     fn test_compatibility_methods() {
         let url = Url::parse("https://example.com:8080").unwrap();
@@ -0,0 +1,246 @@
+use crate::input::{InputError, InputRecord, LineCursor};
+
+/// Record types that describe a captured URL. `revisit` records stand in for
+/// responses that were deduplicated against an earlier capture.
+const CAPTURE_TYPES: &[&str] = &["response", "request", "revisit"];
+
+/// Reads the records of a WARC file and returns one record per capture, taken
+/// from `WARC-Target-URI`. Blocks are skipped by `Content-Length`, so binary
+/// payloads are never decoded; `.warc.gz` files are decompressed before they
+/// get here. For HTTP captures the status code and `Content-Type` of responses
+/// and the method of requests are read from the block's HTTP header.
+pub fn parse_warc(input: &[u8]) -> Result<Vec<Result<InputRecord, InputError>>, InputError> {
+    let mut records = Vec::new();
+    let mut position = 0;
+    let mut lines = LineCursor::new(input);
+
+    loop {
+        while input
+            .get(position)
+            .is_some_and(|&b| b == b'\r' || b == b'\n')
+        {
+            position += 1;
+        }
+        if position >= input.len() {
+            break;
+        }
+
+        let line = lines.line_at(position);
+        let error = |message: String| InputError { line, message };
+        let (header_len, block_offset) = header_end(&input[position..])
+            .ok_or_else(|| error("unterminated WARC header".to_string()))?;
+        let header = String::from_utf8_lossy(&input[position..position + header_len]);
+        let mut header_lines = header.lines();
+        let version = header_lines.next().unwrap_or_default().trim();
+        if !version.starts_with("WARC/") {
+            return Err(error(format!(
+                "expected a WARC/1.x record, found {version:?}"
+            )));
+        }
+
+        let headers: Vec<(String, &str)> = header_lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim()))
+            .collect();
+        let get = |name: &str| {
+            headers
+                .iter()
+                .find(|(header, _)| header == name)
+                .map(|(_, value)| *value)
+        };
+
+        let length: usize = get("content-length")
+            .and_then(|value| value.parse().ok())
+            .ok_or_else(|| error("missing or invalid Content-Length".to_string()))?;
+        let block_start = position + block_offset;
+        let block_end = match block_start.checked_add(length) {
+            Some(end) if end <= input.len() => end,
+            _ => {
+                return Err(error(format!(
+                    "record is truncated: Content-Length {length} runs past the end of the file"
+                )))
+            }
+        };
+        position = block_end;
+
+        let record_type = get("warc-type").unwrap_or_default().to_ascii_lowercase();
+        if !CAPTURE_TYPES.contains(&record_type.as_str()) {
+            continue;
+        }
+        let Some(target) = get("warc-target-uri").filter(|uri| !uri.is_empty()) else {
+            records.push(Err(error(format!(
+                "{record_type} record has no WARC-Target-URI"
+            ))));
+            continue;
+        };
+        let target = target
+            .strip_prefix('<')
+            .and_then(|uri| uri.strip_suffix('>'))
+            .unwrap_or(target);
+
+        let mut record = InputRecord::new(target).at_line(line, None);
+        record.push_field("record_type", record_type.as_str());
+        if let Some(date) = get("warc-date") {
+            record.push_field("timestamp", date);
+        }
+        let is_http = get("content-type").is_some_and(|ct| ct.starts_with("application/http"));
+        if is_http {
+            read_http_header(&input[block_start..block_end], &mut record);
+        }
+        records.push(Ok(record));
+    }
+    Ok(records)
+}
+
+/// Reads the status line or request line and the `Content-Type` of an HTTP
+/// message stored in a WARC block.
+fn read_http_header(block: &[u8], record: &mut InputRecord) {
+    let end = header_end(block).map_or(block.len(), |(end, _)| end);
+    let header = String::from_utf8_lossy(&block[..end]);
+    let mut lines = header.lines();
+    let first = lines.next().unwrap_or_default();
+    let mut parts = first.split_whitespace();
+
+    match (parts.next(), parts.next()) {
+        (Some(version), Some(status)) if version.starts_with("HTTP/") => {
+            if status.chars().all(|c| c.is_ascii_digit()) {
+                record.push_field("status", status);
+            }
+            let content_type = lines.find_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                name.trim()
+                    .eq_ignore_ascii_case("content-type")
+                    .then(|| value.trim())
+            });
+            if let Some(content_type) = content_type.filter(|ct| !ct.is_empty()) {
+                record.push_field("mime_type", content_type);
+            }
+        }
+        (Some(method), Some(_)) if parts.next().is_some_and(|v| v.starts_with("HTTP/")) => {
+            record.push_field("method", method);
+        }
+        _ => {}
+    }
+}
+
+/// Finds the blank line ending a header block, whether its lines end in CRLF
+/// or LF, and returns the length of the header and the offset of the body.
+/// The first blank line wins, so an LF-only header is not extended to a
+/// `\r\n\r\n` further on in the payload.
+fn header_end(input: &[u8]) -> Option<(usize, usize)> {
+    input
+        .iter()
+        .enumerate()
+        .filter(|(_, &b)| b == b'\n')
+        .find_map(|(i, _)| {
+            let rest = &input[i + 1..];
+            let body = if rest.starts_with(b"\n") {
+                i + 2
+            } else if rest.starts_with(b"\r\n") {
+                i + 3
+            } else {
+                return None;
+            };
+            Some((i, body))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn warc_record(headers: &str, block: &[u8]) -> Vec<u8> {
+        let mut record = format!(
+            "WARC/1.1\r\n{headers}Content-Length: {}\r\n\r\n",
+            block.len()
+        )
+        .into_bytes();
+        record.extend_from_slice(block);
+        record.extend_from_slice(b"\r\n\r\n");
+        record
+    }
+
+    #[test]
+    fn test_parse_warc() {
+        let mut warc = warc_record(
+            "WARC-Type: warcinfo\r\nWARC-Date: 2024-05-01T10:00:00Z\r\n",
+            b"software: crawler/1.0\r\n",
+        );
+        warc.extend(warc_record(
+            "WARC-Type: request\r\nWARC-Target-URI: https://example.com/\r\n\
+             WARC-Date: 2024-05-01T10:00:01Z\r\nContent-Type: application/http; msgtype=request\r\n",
+            b"GET / HTTP/1.1\r\nHost: example.com\r\n\r\n",
+        ));
+        warc.extend(warc_record(
+            "WARC-Type: response\r\nWARC-Target-URI: <https://example.com/logo.png>\r\n\
+             WARC-Date: 2024-05-01T10:00:02Z\r\nContent-Type: application/http; msgtype=response\r\n",
+            b"HTTP/1.1 200 OK\r\nContent-Type: image/png\r\n\r\n\x89PNG\r\n\x1a\n\x00\xff\r\n\r\nWARC/1.1\r\n",
+        ));
+        warc.extend(warc_record(
+            "WARC-Type: metadata\r\nWARC-Target-URI: https://example.com/logo.png\r\n",
+            b"outlinks: none\r\n",
+        ));
+
+        let records: Vec<_> = parse_warc(&warc)
+            .unwrap()
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(records.len(), 2);
+
+        assert_eq!(records[0].url, "https://example.com/");
+        assert_eq!(records[0].field("record_type"), Some("request"));
+        assert_eq!(records[0].field("method"), Some("GET"));
+        assert_eq!(records[0].field("status"), None);
+
+        assert_eq!(records[1].url, "https://example.com/logo.png");
+        assert_eq!(records[1].field("record_type"), Some("response"));
+        assert_eq!(records[1].field("timestamp"), Some("2024-05-01T10:00:02Z"));
+        assert_eq!(records[1].field("status"), Some("200"));
+        assert_eq!(records[1].field("mime_type"), Some("image/png"));
+    }
+
+    #[test]
+    fn test_parse_warc_truncated_record() {
+        let mut warc = warc_record(
+            "WARC-Type: response\r\nWARC-Target-URI: https://example.com/\r\n",
+            b"HTTP/1.1 200 OK\r\n\r\n",
+        );
+        warc.extend_from_slice(
+            b"WARC/1.1\r\nWARC-Type: response\r\nContent-Length: 500\r\n\r\nHTTP/1.1",
+        );
+        let err = parse_warc(&warc).unwrap_err();
+        assert!(err.message.contains("truncated"));
+        assert_eq!(err.line, 10);
+    }
+
+    #[test]
+    fn test_parse_warc_content_length_overflow() {
+        let warc = b"WARC/1.1\r\nWARC-Type: response\r\n\
+                     Content-Length: 18446744073709551615\r\n\r\nHTTP/1.1";
+        let err = parse_warc(warc).unwrap_err();
+        assert!(err.message.contains("truncated"));
+    }
+
+    #[test]
+    fn test_parse_warc_lf_headers() {
+        let mut warc = b"WARC/1.1\nWARC-Type: response\nWARC-Target-URI: https://example.com/\n\
+                         Content-Length: 19\n\nHTTP/1.1 200 OK\n\nab\n\n"
+            .to_vec();
+        warc.extend(warc_record(
+            "WARC-Type: request\r\nWARC-Target-URI: https://example.org/\r\n",
+            b"GET / HTTP/1.1\r\n\r\n",
+        ));
+
+        let records = parse_warc(&warc).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].as_ref().unwrap().url, "https://example.com/");
+        assert_eq!(records[1].as_ref().unwrap().url, "https://example.org/");
+        assert_eq!(records[1].as_ref().unwrap().line, Some(10));
+    }
+
+    #[test]
+    fn test_parse_warc_rejects_other_files() {
+        assert!(parse_warc(b"https://example.com/\n").is_err());
+    }
+}
@@ -96,9 +96,12 @@ fn test_sql_format_basic() {
         .arg("--fields")
         .arg("domain,path");
 
-    cmd.assert().success().stdout(predicate::str::contains(
-        "INSERT INTO urls (domain, path) VALUES ('example.com', '/path');",
-    ));
+    cmd.assert().success().stdout(
+        "BEGIN;\n\
+         INSERT INTO \"urls\" (\"domain\", \"path\") VALUES\n    \
+         ('example.com', '/path');\n\
+         COMMIT;\n",
+    );
 }
 
 #[test]
@@ -115,10 +118,12 @@ fn test_sql_format_with_create_table() {
 
     cmd.assert()
         .success()
-        .stdout(predicate::str::contains("CREATE TABLE IF NOT EXISTS urls"))
-        .stdout(predicate::str::contains("domain VARCHAR(253)"))
-        .stdout(predicate::str::contains("path TEXT"))
-        .stdout(predicate::str::contains("INSERT INTO urls"));
+        .stdout(predicate::str::contains(
+            "CREATE TABLE IF NOT EXISTS \"urls\"",
+        ))
+        .stdout(predicate::str::contains("\"domain\" VARCHAR(253)"))
+        .stdout(predicate::str::contains("\"path\" TEXT"))
+        .stdout(predicate::str::contains("INSERT INTO \"urls\""));
 }
 
 #[test]
@@ -135,7 +140,7 @@ fn test_sql_format_custom_table() {
         .arg("my_urls");
 
     cmd.assert().success().stdout(predicate::str::contains(
-        "INSERT INTO my_urls (domain) VALUES ('example.com');",
+        "INSERT INTO \"my_urls\" (\"domain\") VALUES\n    ('example.com');",
     ));
 }
 
@@ -155,7 +160,7 @@ fn test_sql_format_mysql_dialect() {
 
     cmd.assert()
         .success()
-        .stdout(predicate::str::contains("port INT"));
+        .stdout(predicate::str::contains("`port` INT"));
 }
 
 #[test]
@@ -174,7 +179,7 @@ fn test_sql_format_sqlite_dialect() {
 
     cmd.assert()
         .success()
-        .stdout(predicate::str::contains("domain TEXT"));
+        .stdout(predicate::str::contains("\"domain\" TEXT"));
 }
 
 #[test]
@@ -194,7 +199,7 @@ fn test_sql_format_escaping() {
 }
 
 #[test]
-fn test_custom_format_invalid_field() {
+fn test_custom_format_unknown_field_is_missing() {
     let mut cmd = Command::cargo_bin("rexturl").unwrap();
 
     cmd.arg("--urls")
@@ -202,11 +207,40 @@ fn test_custom_format_invalid_field() {
         .arg("--format")
         .arg("custom")
         .arg("--template")
-        .arg("{invalid_field}");
+        .arg("{host} {owner:none}");
 
-    cmd.assert().failure().stderr(predicate::str::contains(
-        "Invalid field name: invalid_field",
-    ));
+    cmd.assert().success().stdout("www.example.com none\n");
+}
+
+#[test]
+fn test_custom_format_csv_columns() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--input-format")
+        .arg("csv")
+        .arg("--format")
+        .arg("custom")
+        .arg("--template")
+        .arg("{url} {owner}")
+        .write_stdin("url,owner\nhttps://example.com/a,ops\n");
+
+    cmd.assert().success().stdout("https://example.com/a ops\n");
+}
+
+#[test]
+fn test_custom_format_empty_field_name() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--urls")
+        .arg("https://www.example.com/path")
+        .arg("--format")
+        .arg("custom")
+        .arg("--template")
+        .arg("{}");
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Empty field name in template"));
 }
 
 #[test]
@@ -239,12 +273,96 @@ fn test_multiple_urls_sql_format() {
         .arg("--fields")
         .arg("domain");
 
+    cmd.assert().success().stdout(predicate::str::contains(
+        "INSERT INTO \"urls\" (\"domain\") VALUES\n    ('example.com'),\n    ('test.com');",
+    ));
+}
+
+#[test]
+fn test_sql_format_batches() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--urls")
+        .arg("https://a.com/")
+        .arg("https://b.com/")
+        .arg("https://c.com/")
+        .arg("--format")
+        .arg("sql")
+        .arg("--fields")
+        .arg("domain")
+        .arg("--sql-batch-size")
+        .arg("2");
+
+    cmd.assert().success().stdout(
+        "BEGIN;\n\
+         INSERT INTO \"urls\" (\"domain\") VALUES\n    ('a.com'),\n    ('b.com');\n\
+         INSERT INTO \"urls\" (\"domain\") VALUES\n    ('c.com');\n\
+         COMMIT;\n",
+    );
+}
+
+#[test]
+fn test_sql_format_mysql_escaping() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--urls")
+        .arg("https://www.example.com/a\\b's")
+        .arg("--format")
+        .arg("sql")
+        .arg("--fields")
+        .arg("path")
+        .arg("--sql-dialect")
+        .arg("mysql");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("START TRANSACTION;\n"))
+        .stdout(predicate::str::contains(
+            "INSERT INTO `urls` (`path`) VALUES\n    ('/a\\\\b''s');",
+        ));
+}
+
+#[test]
+fn test_sql_format_sqlite_indexes() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--urls")
+        .arg("https://www.example.com/path")
+        .arg("--format")
+        .arg("sql")
+        .arg("--fields")
+        .arg("domain,path")
+        .arg("--sql-create-table")
+        .arg("--sql-indexes")
+        .arg("--sql-dialect")
+        .arg("sqlite");
+
     cmd.assert()
         .success()
         .stdout(predicate::str::contains(
-            "INSERT INTO urls (domain) VALUES ('example.com');",
+            "\"id\" INTEGER PRIMARY KEY AUTOINCREMENT",
         ))
         .stdout(predicate::str::contains(
-            "INSERT INTO urls (domain) VALUES ('test.com');",
-        ));
+            "CREATE INDEX IF NOT EXISTS \"urls_domain_idx\" ON \"urls\" (\"domain\");",
+        ))
+        .stdout(predicate::str::contains("SERIAL").not());
+}
+
+#[test]
+fn test_sql_format_rejects_invalid_table_name() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--urls")
+        .arg("https://www.example.com/path")
+        .arg("--format")
+        .arg("sql")
+        .arg("--fields")
+        .arg("domain")
+        .arg("--sql-table")
+        .arg("urls; DROP TABLE users");
+
+    cmd.assert()
+        .code(1)
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("Error: Invalid --sql-table"));
 }
@@ -0,0 +1,689 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::io::Write;
+
+#[test]
+fn test_extract_from_text() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--extract")
+        .arg("--fields")
+        .arg("hostname")
+        .write_stdin(
+        "2024-01-01 GET https://api.example.com/v1/users, referer (https://www.example.org/).\n\
+             Contact \"http://support.example.net/help\" for details.\n",
+    );
+
+    cmd.assert()
+        .success()
+        .stdout("api.example.com\nwww.example.org\nsupport.example.net\n");
+}
+
+#[test]
+fn test_extract_from_html_with_base() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--extract").arg("html").write_stdin(
+        r#"<head><base href="https://example.com/docs/"></head>
+<a href="intro.html">Intro</a>
+<img src="/img/a.png" srcset="/img/a-2x.png 2x">"#,
+    );
+
+    cmd.assert().success().stdout(
+        "https://example.com/docs/intro.html\n\
+         https://example.com/img/a.png\n\
+         https://example.com/img/a-2x.png\n",
+    );
+}
+
+#[test]
+fn test_extract_from_markdown() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--extract")
+        .arg("markdown")
+        .arg("--base")
+        .arg("https://example.com/")
+        .arg("--fields")
+        .arg("url")
+        .write_stdin("Read [the guide](guide.md) or visit <https://rust-lang.org>.\n");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("https://example.com/guide.md"))
+        .stdout(predicate::str::contains("https://rust-lang.org"));
+}
+
+#[test]
+fn test_clf_input_with_log_host() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--input-format")
+        .arg("clf")
+        .arg("--log-host")
+        .arg("example.com")
+        .arg("--fields")
+        .arg("url,method,status,bytes")
+        .write_stdin(
+            "203.0.113.7 - - [10/Oct/2024:13:55:36 +0000] \"GET /index.html?a=1 HTTP/1.1\" 200 5120\n",
+        );
+
+    cmd.assert()
+        .success()
+        .stdout("http://example.com/index.html?a=1 GET 200 5120\n");
+}
+
+#[test]
+fn test_combined_input_with_vhost() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--input-format")
+        .arg("combined")
+        .arg("--fields")
+        .arg("url,referrer,user_agent")
+        .arg("--filter")
+        .arg("status >= 400")
+        .write_stdin(
+            "www.example.com:443 203.0.113.7 - - [10/Oct/2024:13:55:36 +0000] \"GET /ok HTTP/1.1\" 200 12 \"-\" \"curl/8.5.0\"\n\
+             www.example.com:443 203.0.113.7 - - [10/Oct/2024:13:55:37 +0000] \"GET /missing HTTP/1.1\" 404 0 \"https://www.example.com/ok\" \"curl/8.5.0\"\n",
+        );
+
+    cmd.assert()
+        .success()
+        .stdout("https://www.example.com/missing https://www.example.com/ok curl/8.5.0\n");
+}
+
+#[test]
+fn test_nginx_json_input() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--input-format")
+        .arg("nginx-json")
+        .arg("--fields")
+        .arg("hostname,path,method,remote_addr")
+        .write_stdin(
+            r#"{"remote_addr":"203.0.113.7","host":"api.example.com","request":"POST /v1/login HTTP/1.1","status":"401"}"#,
+        );
+
+    cmd.assert()
+        .success()
+        .stdout("api.example.com /v1/login POST 203.0.113.7\n");
+}
+
+#[test]
+fn test_access_log_strict_reports_bad_lines() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--input-format")
+        .arg("clf")
+        .arg("--log-host")
+        .arg("example.com")
+        .arg("--strict")
+        .write_stdin(
+            "203.0.113.7 - - [10/Oct/2024:13:55:36 +0000] \"GET / HTTP/1.1\" 200 1\nnot a log line\n",
+        );
+
+    cmd.assert()
+        .code(2)
+        .stdout("http://example.com/\n")
+        .stderr(predicate::str::contains("line 2"));
+}
+
+#[test]
+fn test_har_input() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--input-format")
+        .arg("har")
+        .arg("--fields")
+        .arg("method,status,mime_type,path")
+        .write_stdin(
+            r#"{"log": {"version": "1.2", "entries": [
+  {"request": {"method": "GET", "url": "https://example.com/"},
+   "response": {"status": 200, "content": {"mimeType": "text/html"}}},
+  {"request": {"method": "POST", "url": "https://example.com/api/login"},
+   "response": {"status": 401, "content": {"mimeType": "application/json"}}}
+]}}"#,
+        );
+
+    cmd.assert()
+        .success()
+        .stdout("GET 200 text/html /\nPOST 401 application/json /api/login\n");
+}
+
+#[test]
+fn test_burp_input() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--input-format")
+        .arg("burp")
+        .arg("--fields")
+        .arg("url,mime_type")
+        .arg("--filter")
+        .arg("status == 200")
+        .write_stdin(
+            r#"<?xml version="1.0"?>
+<items burpVersion="2024.1">
+  <item>
+    <url><![CDATA[https://example.com/index.php?id=1]]></url>
+    <method><![CDATA[GET]]></method>
+    <status>200</status>
+    <mimetype>HTML</mimetype>
+  </item>
+  <item>
+    <url><![CDATA[https://example.com/admin]]></url>
+    <method><![CDATA[GET]]></method>
+    <status>403</status>
+    <mimetype>HTML</mimetype>
+  </item>
+</items>"#,
+        );
+
+    cmd.assert()
+        .success()
+        .stdout("https://example.com/index.php?id=1 HTML\n");
+}
+
+#[test]
+fn test_har_input_invalid_document() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--input-format")
+        .arg("har")
+        .write_stdin("{\"log\": ");
+
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains("invalid HAR"));
+}
+
+#[test]
+fn test_csv_input_passes_columns_through() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--input-format")
+        .arg("csv")
+        .arg("--url-column")
+        .arg("referrer")
+        .arg("--domain")
+        .arg("--format")
+        .arg("tsv")
+        .arg("--header")
+        .write_stdin(
+            "id,referrer,campaign\n\
+             1,https://www.example.com/landing,spring\n\
+             2,https://shop.example.org/cart,\n",
+        );
+
+    cmd.assert()
+        .success()
+        .stdout("domain\tid\tcampaign\nexample.com\t1\tspring\nexample.org\t2\t\\N\n");
+}
+
+#[test]
+fn test_csv_input_renames_clashing_columns() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--input-format")
+        .arg("csv")
+        .arg("--format")
+        .arg("tsv")
+        .arg("--header")
+        .write_stdin("url,path,line\nhttps://example.com/a,/old,7\n");
+
+    cmd.assert()
+        .success()
+        .stdout("url\tinput.path\tinput.line\nhttps://example.com/a\t/old\t7\n")
+        .stderr(predicate::str::contains(
+            "Input column \"path\" clashes with a built-in field, renamed to \"input.path\"",
+        ));
+}
+
+#[test]
+fn test_csv_input_unknown_column() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--input-format")
+        .arg("csv")
+        .arg("--url-column")
+        .arg("link")
+        .write_stdin("id,url\n1,https://example.com\n");
+
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains("no column named \"link\""));
+}
+
+#[test]
+fn test_jsonl_input_with_nested_url_field() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--input-format")
+        .arg("jsonl")
+        .arg("--url-field")
+        .arg("request.url")
+        .arg("--format")
+        .arg("jsonl")
+        .arg("--fields")
+        .arg("hostname,user.id,request.method")
+        .arg("--filter")
+        .arg("request.method == \"POST\"")
+        .write_stdin(
+            r#"{"user": {"id": 7}, "request": {"url": "https://api.example.com/login", "method": "POST"}}
+{"user": {"id": 8}, "request": {"url": "https://www.example.com/", "method": "GET"}}
+"#,
+        );
+
+    cmd.assert().success().stdout(
+        "{\"hostname\":\"api.example.com\",\"request.method\":\"POST\",\"user.id\":\"7\"}\n",
+    );
+}
+
+#[test]
+fn test_line_source_and_input_fields() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--fields")
+        .arg("line,source,domain,input")
+        .arg("--format")
+        .arg("tsv")
+        .write_stdin("https://www.example.com/a\nnot a url\n  https://shop.example.org/b \n");
+
+    cmd.assert().success().stdout(
+        "1\t-\texample.com\thttps://www.example.com/a\n\
+         3\t-\texample.org\t  https://shop.example.org/b \n",
+    );
+}
+
+#[test]
+fn test_line_field_with_extract() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--extract")
+        .arg("--format")
+        .arg("custom")
+        .arg("--template")
+        .arg("{line}: {url}")
+        .write_stdin("intro\nsee https://example.com/docs\n\nand https://example.org/\n");
+
+    cmd.assert()
+        .success()
+        .stdout("2: https://example.com/docs\n4: https://example.org/\n");
+}
+
+#[test]
+fn test_multiple_input_files_with_globs_and_gzip() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.txt"), "https://a.example.com/1\n").unwrap();
+    std::fs::write(dir.path().join("b.txt"), "https://b.example.com/2\n").unwrap();
+    let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+    gzip.write_all(b"https://c.example.com/3\n").unwrap();
+    std::fs::write(dir.path().join("c.log.gz"), gzip.finish().unwrap()).unwrap();
+
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+    cmd.current_dir(dir.path())
+        .arg("*.txt")
+        .arg("--input")
+        .arg("c.log.gz")
+        .arg("--input")
+        .arg("-")
+        .arg("--fields")
+        .arg("source,hostname")
+        .write_stdin("https://d.example.com/4\n");
+
+    cmd.assert().success().stdout(
+        "a.txt a.example.com\n\
+         b.txt b.example.com\n\
+         c.log.gz c.example.com\n\
+         - d.example.com\n",
+    );
+}
+
+#[test]
+fn test_input_glob_without_matches() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+    cmd.current_dir(dir.path()).arg("*.txt");
+
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains("no files match *.txt"));
+}
+
+#[test]
+fn test_input_literal_file_with_glob_characters() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("data[1].txt"),
+        "https://literal.example.com/\n",
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("data1.txt"), "https://glob.example.com/\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+    cmd.current_dir(dir.path())
+        .arg("data[1].txt")
+        .arg("--fields")
+        .arg("source,hostname");
+
+    cmd.assert()
+        .success()
+        .stdout("data[1].txt literal.example.com\n");
+}
+
+#[test]
+fn test_input_file_errors_name_the_file() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("access.log"), "garbage\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+    cmd.current_dir(dir.path())
+        .arg("access.log")
+        .arg("--input-format")
+        .arg("clf")
+        .arg("--strict");
+
+    cmd.assert()
+        .code(2)
+        .stderr(predicate::str::contains("access.log: line 1:"));
+}
+
+#[test]
+fn test_gzipped_sitemap_input() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+    gzip.write_all(
+        br#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <url><loc>https://example.com/</loc><priority>1.0</priority></url>
+  <url><loc>https://example.com/blog/</loc><lastmod>2024-05-01</lastmod><changefreq>weekly</changefreq></url>
+</urlset>"#,
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("sitemap.xml.gz"), gzip.finish().unwrap()).unwrap();
+
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+    cmd.current_dir(dir.path())
+        .arg("sitemap.xml.gz")
+        .arg("--input-format")
+        .arg("sitemap")
+        .arg("--format")
+        .arg("tsv")
+        .arg("--fields")
+        .arg("path,lastmod,changefreq,priority");
+
+    cmd.assert()
+        .success()
+        .stdout("/\t\\N\t\\N\t1.0\n/blog/\t2024-05-01\tweekly\t\\N\n");
+}
+
+#[test]
+fn test_sitemap_index_input() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--input-format")
+        .arg("sitemap")
+        .arg("--fields")
+        .arg("kind,url")
+        .write_stdin(
+            r#"<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <sitemap><loc>https://example.com/sitemap-pages.xml</loc></sitemap>
+  <sitemap><loc>https://example.com/sitemap-posts.xml.gz</loc></sitemap>
+</sitemapindex>"#,
+        );
+
+    cmd.assert().success().stdout(
+        "sitemap https://example.com/sitemap-pages.xml\n\
+         sitemap https://example.com/sitemap-posts.xml.gz\n",
+    );
+}
+
+#[test]
+fn test_robots_input() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--input-format")
+        .arg("robots")
+        .arg("--base")
+        .arg("https://example.com")
+        .arg("--fields")
+        .arg("directive,user_agent,url")
+        .write_stdin(
+            "User-agent: *\n\
+             Disallow: /admin/\n\
+             Allow: /admin/public\n\
+             Sitemap: https://cdn.example.com/sitemap.xml\n",
+        );
+
+    cmd.assert().success().stdout(
+        "disallow * https://example.com/admin/\n\
+         allow * https://example.com/admin/public\n\
+         sitemap \\N https://cdn.example.com/sitemap.xml\n",
+    );
+}
+
+#[test]
+fn test_robots_input_without_base() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--input-format")
+        .arg("robots")
+        .write_stdin("User-agent: *\nDisallow: /admin/\n");
+
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains("need --base"));
+}
+
+fn warc_record(headers: &str, block: &[u8]) -> Vec<u8> {
+    let mut record = format!(
+        "WARC/1.0\r\n{headers}Content-Length: {}\r\n\r\n",
+        block.len()
+    )
+    .into_bytes();
+    record.extend_from_slice(block);
+    record.extend_from_slice(b"\r\n\r\n");
+    record
+}
+
+#[test]
+fn test_warc_gz_input() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut archive = Vec::new();
+    for record in [
+        warc_record(
+            "WARC-Type: warcinfo\r\n",
+            b"software: test-crawler\r\n",
+        ),
+        warc_record(
+            "WARC-Type: response\r\nWARC-Target-URI: https://example.com/\r\n\
+             WARC-Date: 2024-05-01T10:00:00Z\r\nContent-Type: application/http; msgtype=response\r\n",
+            b"HTTP/1.1 200 OK\r\nContent-Type: text/html\r\n\r\n<html>\xff\xfe</html>",
+        ),
+        warc_record(
+            "WARC-Type: response\r\nWARC-Target-URI: https://example.com/gone\r\n\
+             WARC-Date: 2024-05-01T10:00:01Z\r\nContent-Type: application/http; msgtype=response\r\n",
+            b"HTTP/1.1 404 Not Found\r\n\r\n",
+        ),
+    ] {
+        let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        gzip.write_all(&record).unwrap();
+        archive.extend(gzip.finish().unwrap());
+    }
+    std::fs::write(dir.path().join("crawl.warc.gz"), archive).unwrap();
+
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+    cmd.current_dir(dir.path())
+        .arg("crawl.warc.gz")
+        .arg("--input-format")
+        .arg("warc")
+        .arg("--format")
+        .arg("csv")
+        .arg("--fields")
+        .arg("url,record_type,status,timestamp");
+
+    cmd.assert().success().stdout(
+        "https://example.com/,response,200,2024-05-01T10:00:00Z\n\
+         https://example.com/gone,response,404,2024-05-01T10:00:01Z\n",
+    );
+}
+
+#[test]
+fn test_extract_from_js_with_base_and_offsets() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--extract")
+        .arg("js")
+        .arg("--base")
+        .arg("https://app.example.com/")
+        .arg("--format")
+        .arg("tsv")
+        .arg("--fields")
+        .arg("offset,path,hostname")
+        .write_stdin(
+            r#"// fetch("/old/endpoint")
+const u="/api/v2/users";fetch("https:\/\/auth.example.com\/token",{method:"POST"});"#,
+        );
+
+    cmd.assert().success().stdout(
+        "35\t/api/v2/users\tapp.example.com\n\
+         57\t/token\tauth.example.com\n",
+    );
+}
+
+#[test]
+fn test_records_round_trip() {
+    let first = Command::cargo_bin("rexturl")
+        .unwrap()
+        .args(["--input-format", "csv", "--format", "jsonl"])
+        .args(["--fields", "url,status"])
+        .write_stdin(
+            "url,status\n\
+             https://example.com:8443/a?x=1,200\n\
+             https://api.example.org/b,503\n",
+        )
+        .output()
+        .unwrap();
+    assert!(first.status.success());
+
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+    cmd.args(["--input-format", "records", "--format", "tsv"])
+        .args(["--filter", "status >= 500 || port == 8443"])
+        .args(["--fields", "domain,port,path,status,line"])
+        .write_stdin(first.stdout);
+
+    cmd.assert().success().stdout(
+        "example.com\t8443\t/a\t200\t1\n\
+         example.org\t\\N\t/b\t503\t2\n",
+    );
+}
+
+#[test]
+fn test_records_rebuild_url_from_components() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.args(["--input-format", "records", "--fields", "url,hostname,path"])
+        .write_stdin(
+            r#"{"urls": [
+  {"scheme": "https", "hostname": "example.com", "port": "8080", "path": "/x", "query": "a=1"},
+  {"scheme": "http", "subdomain": "www", "domain": "example.org"}
+]}"#,
+        );
+
+    cmd.assert().success().stdout(
+        "https://example.com:8080/x?a=1 example.com /x\n\
+         http://www.example.org/ www.example.org /\n",
+    );
+}
+
+#[test]
+fn test_assemble_from_tabular_output() {
+    let table = Command::cargo_bin("rexturl")
+        .unwrap()
+        .args(["--format", "tsv", "--header"])
+        .args(["--fields", "scheme,hostname,port,path,query"])
+        .write_stdin("https://example.com:8443/a?x=1\nhttp://[::1]:8080/b\n")
+        .output()
+        .unwrap();
+    assert!(table.status.success());
+    let edited = String::from_utf8(table.stdout)
+        .unwrap()
+        .replace("/a\t", "/new path\t");
+
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+    cmd.args(["--input-format", "assemble"]).write_stdin(edited);
+
+    cmd.assert()
+        .success()
+        .stdout("https://example.com:8443/new%20path?x=1\nhttp://[::1]:8080/b\n");
+}
+
+#[test]
+fn test_assemble_reads_custom_csv_delimiter() {
+    let table = Command::cargo_bin("rexturl")
+        .unwrap()
+        .args(["--format", "csv", "--header", "--csv-delimiter", ";"])
+        .args(["--csv-quote", "'", "--fields", "scheme,hostname,path"])
+        .write_stdin("https://example.com/a;b\n")
+        .output()
+        .unwrap();
+    assert!(table.status.success());
+
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+    cmd.args(["--input-format", "assemble", "--csv-delimiter", ";"])
+        .args(["--csv-quote", "'"])
+        .write_stdin(table.stdout);
+
+    cmd.assert().success().stdout("https://example.com/a;b\n");
+}
+
+#[test]
+fn test_assemble_with_component_columns() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.args(["--input-format", "assemble", "--strict"])
+        .args(["--component-columns", "scheme=proto,host=server,path=uri"])
+        .args(["--fields", "url,owner"])
+        .write_stdin("proto,server,uri,owner\nhttps,example.com,/x,ops\nhttps,,/y,dev\n");
+
+    cmd.assert()
+        .code(2)
+        .stdout("https://example.com/x ops\n")
+        .stderr(predicate::str::contains("line 3: missing host"));
+}
+
+#[test]
+fn test_assemble_rejects_unknown_component() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.args(["--input-format", "assemble"])
+        .args(["--component-columns", "server=host"])
+        .write_stdin("host\nexample.com\n");
+
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains("unknown component \"server\""));
+}
+
+#[test]
+fn test_records_round_trip_typed_json() {
+    let first = Command::cargo_bin("rexturl")
+        .unwrap()
+        .args(["--format", "json", "--typed", "--pretty"])
+        .args(["--fields", "url,port,path,query,line"])
+        .write_stdin("https://example.com:8443/a/?tag=y&id=7&tag=x\nhttp://example.org/\n")
+        .output()
+        .unwrap();
+    assert!(first.status.success());
+
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+    cmd.args(["--input-format", "records", "--format", "tsv"])
+        .args(["--filter", "port == 8443"])
+        .args(["--fields", "path,query,port"])
+        .write_stdin(first.stdout);
+
+    cmd.assert()
+        .success()
+        .stdout("/a/\t?tag=y&id=7&tag=x\t8443\n");
+}
@@ -166,3 +166,453 @@ fn test_all_flag() {
             s.contains("user") && s.contains("8080") && s.contains("www.example.com")
         }));
 }
+
+#[test]
+fn test_risk_scoring() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--urls")
+        .arg("http://paypal.com.account-verify.io:8080/login")
+        .arg("--fields")
+        .arg("domain,risk_score,risk_reasons")
+        .arg("--format")
+        .arg("tsv");
+
+    cmd.assert().success().stdout(predicate::str::contains(
+        "account-verify.io\t35\tnonstandard_port,brand_in_subdomain",
+    ));
+}
+
+#[test]
+fn test_risk_flag_adds_fields() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--urls").arg("http://192.168.0.1/").arg("--risk");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("http://192.168.0.1/ 30 ip_host"));
+}
+
+#[test]
+fn test_protect_list_flags_homographs() {
+    let protect_list = create_url_file(&["# brands", "paypal.com"]);
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--urls")
+        .arg("https://xn--ypal-43d9g.com/signin")
+        .arg("https://www.paypal.com/")
+        .arg("--fields")
+        .arg("hostname,mixed_script,homograph_of")
+        .arg("--protect-list")
+        .arg(protect_list.path())
+        .arg("--format")
+        .arg("tsv");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "xn--ypal-43d9g.com\ttrue\tpaypal.com",
+        ))
+        .stdout(predicate::str::contains("www.paypal.com\tfalse\t\\N"));
+}
+
+#[test]
+fn test_similar_to_reports_typosquats() {
+    let watch_list = create_url_file(&["example.com"]);
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--urls")
+        .arg("https://examp1e.com/")
+        .arg("https://www.exmaple.com/")
+        .arg("https://example-login.com/")
+        .arg("--fields")
+        .arg("domain,similar_to,mutation")
+        .arg("--similar-to")
+        .arg(watch_list.path());
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "examp1e.com example.com homoglyph",
+        ))
+        .stdout(predicate::str::contains(
+            "exmaple.com example.com transposition",
+        ))
+        .stdout(predicate::str::contains(
+            "example-login.com example.com combosquat",
+        ));
+}
+
+#[test]
+fn test_scope_filters_out_of_scope_urls() {
+    let scope = create_url_file(&["*.example.com", "!admin.example.com", "10.0.0.0/8"]);
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--urls")
+        .arg("https://www.example.com/")
+        .arg("https://admin.example.com/")
+        .arg("http://10.1.2.3/")
+        .arg("https://other.org/")
+        .arg("--fields")
+        .arg("hostname")
+        .arg("--scope")
+        .arg(scope.path());
+
+    cmd.assert().success().stdout("www.example.com\n10.1.2.3\n");
+}
+
+#[test]
+fn test_scope_annotate() {
+    let scope = create_url_file(&["*.example.com", "!admin.example.com"]);
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--urls")
+        .arg("https://www.example.com/")
+        .arg("https://admin.example.com/")
+        .arg("--fields")
+        .arg("hostname,in_scope")
+        .arg("--scope")
+        .arg(scope.path())
+        .arg("--scope-annotate");
+
+    cmd.assert()
+        .success()
+        .stdout("www.example.com true\nadmin.example.com false\n");
+}
+
+#[test]
+fn test_filter_expression() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--urls")
+        .arg("https://example.com:8080/api/users")
+        .arg("https://example.com:443/api/users")
+        .arg("https://example.com/docs")
+        .arg("https://other.org/api/users")
+        .arg("--filter")
+        .arg(r#"domain == "example.com" && port != "443" && path ~ "^/api/""#);
+
+    cmd.assert()
+        .success()
+        .stdout("https://example.com:8080/api/users\n");
+}
+
+#[test]
+fn test_filter_parse_error() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--urls")
+        .arg("https://example.com")
+        .arg("--filter")
+        .arg(r#"domain in ["a.com" "b.com"]"#);
+
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "Invalid filter expression: at column 20: expected ',' or ']'",
+    ));
+}
+
+#[test]
+fn test_csv_output_quotes_values() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--urls")
+        .arg("https://example.com/search?q=hi,%20there&tag=\"x\"")
+        .arg("--fields")
+        .arg("hostname,query")
+        .arg("--format")
+        .arg("csv")
+        .arg("--header");
+
+    cmd.assert()
+        .success()
+        .stdout("hostname,query\nexample.com,\"?q=hi,%20there&tag=\"\"x\"\"\"\n");
+}
+
+#[test]
+fn test_csv_output_delimiter_quote_and_bom() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--urls")
+        .arg("https://example.com/a;b?q=1")
+        .arg("--fields")
+        .arg("path,query,fragment")
+        .arg("--format")
+        .arg("csv")
+        .arg("--csv-delimiter")
+        .arg(";")
+        .arg("--csv-quote")
+        .arg("'")
+        .arg("--bom");
+
+    cmd.assert().success().stdout("\u{feff}'/a;b';?q=1;\\N\n");
+}
+
+#[test]
+fn test_tsv_output_escapes_values() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--input-format")
+        .arg("jsonl")
+        .arg("--fields")
+        .arg("url,note")
+        .arg("--format")
+        .arg("tsv")
+        .write_stdin(r#"{"url": "https://example.com/", "note": "a\tb\nc\\d"}"#);
+
+    cmd.assert()
+        .success()
+        .stdout("https://example.com/\ta\\tb\\nc\\\\d\n");
+}
+
+#[test]
+fn test_typed_jsonl_output() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--urls")
+        .arg("https://example.com:8443/v1/users?id=7&sort=name")
+        .arg("--fields")
+        .arg("hostname,port,path,query,fragment")
+        .arg("--format")
+        .arg("jsonl")
+        .arg("--typed");
+
+    cmd.assert().success().stdout(
+        "{\"fragment\":null,\"hostname\":\"example.com\",\"path\":[\"v1\",\"users\"],\
+         \"port\":8443,\"query\":[[\"id\",\"7\"],[\"sort\",\"name\"]]}\n",
+    );
+}
+
+#[test]
+fn test_json_schema_flag() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--json-schema")
+        .arg("--typed")
+        .arg("--fields")
+        .arg("domain,port");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "\"$schema\": \"https://json-schema.org/draft/2020-12/schema\"",
+        ))
+        .stdout(predicate::str::contains("\"maximum\": 65535"))
+        .stdout(predicate::str::contains(
+            "\"required\": [\n    \"domain\",\n    \"port\"\n  ]",
+        ));
+}
+
+#[test]
+fn test_color_always_highlights_components() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--urls")
+        .arg("https://example.com:8080/a?b=1")
+        .arg("--fields")
+        .arg("url,fragment")
+        .arg("--color")
+        .arg("always")
+        .env_remove("REXTURL_COLORS");
+
+    cmd.assert().success().stdout(
+        "\x1b[36mhttps\x1b[0m://\x1b[1;34mexample.com\x1b[0m:\x1b[33m8080\x1b[0m\
+         \x1b[32m/a\x1b[0m\x1b[35m?b=1\x1b[0m \x1b[2m\\N\x1b[0m\n",
+    );
+}
+
+#[test]
+fn test_color_palette_from_env_and_flag() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--urls")
+        .arg("https://example.com/")
+        .arg("--fields")
+        .arg("scheme,hostname,path")
+        .arg("--color")
+        .arg("always")
+        .arg("--colors")
+        .arg("path=")
+        .env("REXTURL_COLORS", "scheme=1:host=4");
+
+    cmd.assert()
+        .success()
+        .stdout("\x1b[1mhttps\x1b[0m \x1b[4mexample.com\x1b[0m /\n");
+}
+
+#[test]
+fn test_color_auto_is_off_when_piped() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--urls")
+        .arg("https://example.com/")
+        .env_remove("NO_COLOR");
+
+    cmd.assert().success().stdout("https://example.com/\n");
+}
+
+#[test]
+fn test_color_invalid_palette_warns() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--urls")
+        .arg("https://example.com/")
+        .arg("--fields")
+        .arg("path")
+        .arg("--color")
+        .arg("always")
+        .env("REXTURL_COLORS", "path=green");
+
+    cmd.assert()
+        .success()
+        .stdout("\x1b[32m/\x1b[0m\n")
+        .stderr(predicate::str::contains("Warning: Invalid REXTURL_COLORS"));
+}
+
+#[test]
+fn test_explain_subcommand() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("explain").arg("http://[::1]:8080/a%20b?x=1");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "              http://[::1]:8080/a%20b?x=1\n\
+             scheme        ^^^^\n\
+             host                 ^^^^^\n\
+             port                       ^^^^\n\
+             path                           ^^^^^^\n\
+             query                                 ^^^\n",
+        ))
+        .stdout(predicate::str::contains("  type        IPv6 address\n"))
+        .stdout(predicate::str::contains("  decoded     /a b\n"));
+}
+
+#[test]
+fn test_explain_reports_unparsable_urls() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("explain")
+        .arg("not-a-url")
+        .arg("https://example.com/")
+        .arg("--color")
+        .arg("never");
+
+    cmd.assert()
+        .code(1)
+        .stdout(predicate::str::contains("host          example.com"))
+        .stderr(predicate::str::contains("Error: Cannot parse not-a-url"));
+}
+
+#[test]
+fn test_file_named_explain_after_double_dash() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("explain"), "https://example.com/a\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+    cmd.current_dir(dir.path()).arg("--").arg("explain");
+
+    cmd.assert().success().stdout("https://example.com/a\n");
+}
+
+#[test]
+fn test_table_format() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--urls")
+        .arg("https://example.com:8080/a")
+        .arg("http://x.org/")
+        .arg("--fields")
+        .arg("hostname,port")
+        .arg("--format")
+        .arg("table")
+        .arg("--table-style")
+        .arg("ascii");
+
+    cmd.assert().success().stdout(
+        "+-------------+------+\n\
+         | hostname    | port |\n\
+         +-------------+------+\n\
+         | example.com | 8080 |\n\
+         | x.org       |   \\N |\n\
+         +-------------+------+\n",
+    );
+}
+
+#[test]
+fn test_markdown_format() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--urls")
+        .arg("https://example.com:8080/a?x=1|2")
+        .arg("http://example.org/")
+        .arg("--fields")
+        .arg("url,port")
+        .arg("--format")
+        .arg("markdown");
+
+    cmd.assert().success().stdout(
+        "| url | port |\n\
+         | --- | ---: |\n\
+         | https://example.com:8080/a?x=1\\|2 | 8080 |\n\
+         | http://example.org/ | \\N |\n",
+    );
+}
+
+#[test]
+fn test_html_format_groups_by_domain() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--urls")
+        .arg("https://www.example.com/")
+        .arg("https://example.org/")
+        .arg("https://api.example.com/")
+        .arg("--format")
+        .arg("html")
+        .arg("--group-by-domain");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("<!DOCTYPE html>"))
+        .stdout(predicate::str::contains(
+            "<h2>example.com <span class=\"count\">(2)</span></h2>",
+        ))
+        .stdout(predicate::str::contains(
+            "<h2>example.org <span class=\"count\">(1)</span></h2>",
+        ));
+}
+
+#[test]
+fn test_xml_format() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--urls")
+        .arg("https://example.com/a?x=1&y=<2>")
+        .arg("--fields")
+        .arg("domain,query")
+        .arg("--format")
+        .arg("xml");
+
+    cmd.assert().success().stdout(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?><urls count=\"1\"><record>\
+         <domain>example.com</domain><query>?x=1&amp;y=&lt;2&gt;</query></record></urls>\n",
+    );
+}
+
+#[test]
+fn test_yaml_format() {
+    let mut cmd = Command::cargo_bin("rexturl").unwrap();
+
+    cmd.arg("--urls")
+        .arg("https://example.com:8080/a")
+        .arg("--fields")
+        .arg("url,port")
+        .arg("--format")
+        .arg("yaml")
+        .arg("--pretty");
+
+    cmd.assert()
+        .success()
+        .stdout("urls:\n  - url: \"https://example.com:8080/a\"\n    port: \"8080\"\n");
+}